@@ -1,4 +1,18 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::ops; // Трейты операторов не импортируются по имени, чтобы a.add(&b) вызывал собственный метод, а не Add::add, перемещающий a
+use std::str::FromStr;
+
 const BITS: u32 = 32; // Константа, которая определяет количество бит в одном числе
+const KARATSUBA_THRESHOLD: usize = 32; // Количество разрядов, начиная с которого умножение выполняется методом Карацубы
+const RADIX_DC_THRESHOLD: usize = 32; // Количество разрядов, начиная с которого перевод между системами счисления идет методом "разделяй и властвуй"
+const NEWTON_THRESHOLD: usize = 64; // Количество разрядов, начиная с которого обратное число считается методом Ньютона
+const SMALL_PRIME_LIMIT: usize = 4096; // Граница таблицы малых простых для пробного деления
+const SMALL_PRIMES: [u32; 564] = small_primes(); // Все простые меньше SMALL_PRIME_LIMIT
+const PRIME_SEARCH_WINDOW: u64 = 1 << 16; // Сколько последовательных кандидатов проверяется от одной случайной точки
+const PRIME_GEN_ROUNDS: usize = 8; // Дополнительные раунды Миллера–Рабина для сгенерированных простых сверх теста Бэйли–PSW
+const MILLER_RABIN_BASES_U64: [u32; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37]; // Детерминированный набор оснований для n < 2^64
 
 #[derive(Debug)]
 struct BigNumber {
@@ -16,6 +30,7 @@ impl Clone for BigNumber {
 }
 
 impl BigNumber {
+    #[cfg(test)]
     fn new() -> Self {  // Создание новой структуры BigNumber
         Self {         // Возвращает новую структуру с массивом данных из одного элемента и пустым шестнадцатеричным представлением
             data: vec![0],
@@ -39,300 +54,2958 @@ impl BigNumber {
         self.data = temp_data;  // Сохранение массива чисел
     }
 
+    #[cfg(test)]
     fn get_hex(&self) -> String {   // Метод получения шестнадцатеричного представления числа
         self.hex.clone()     // Возвращает копию шестнадцатеричного представления
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 enum Sign {
     Positive,  // Положительный знак
     Negative,  // Отрицательный знак
 }
 
-#[derive(Clone)]
-pub struct MyBigInt {
-    sign: Sign,  // Знак числа
-    digits: Vec<u32>,  // Вектор цифр числа в порядке от младших разрядов к старшим
-    other: BigNumber,  // Вспомогательное поле для хранения числа в виде строки шестнадцатеричных цифр
+impl Sign {
+    fn swap(self) -> Sign { // Возвращает противоположный знак
+        match self {
+            Sign::Positive => Sign::Negative,
+            Sign::Negative => Sign::Positive,
+        }
+    }
 }
 
-impl Default for MyBigInt {
-    fn default() -> Self {
-        Self {
-            digits: vec![0],  // По умолчанию число равно нулю
-            sign: Sign::Positive, // По умолчанию число положительное
-            other: BigNumber {
-                data: vec![],
-                hex: String::new(),
-            },
+// Сравнение модулей двух чисел, заданных разрядами от младших к старшим (старшие нули не учитываются)
+fn cmp_digits(a: &[u32], b: &[u32]) -> Ordering {
+    let a_len = a.len() - a.iter().rev().take_while(|&&d| d == 0).count();
+    let b_len = b.len() - b.iter().rev().take_while(|&&d| d == 0).count();
+    if a_len != b_len {
+        return a_len.cmp(&b_len);
+    }
+    for i in (0..a_len).rev() {
+        if a[i] != b[i] {
+            return a[i].cmp(&b[i]);
         }
     }
+    Ordering::Equal
 }
 
-impl MyBigInt {  // Определение структуры MyBigInt
-    pub fn set_hex(&mut self, hex: &str) {  // Публичный метод set_hex, который принимает строку hex и изменяет значение поля other структуры
-        self.other.set_hex(hex);
+// Сложение модулей: |a| + |b|
+fn add_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &x) in long.iter().enumerate() {
+        let sum = x as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> BITS;
     }
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+    result
+}
 
-    pub fn get_hex(&self) -> String { // Публичный метод get_hex, который возвращает значение поля other структуры в виде строки
-        self.other.get_hex()
+// Вычитание модулей: |a| - |b|, требуется |a| >= |b|
+fn sub_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &x) in a.iter().enumerate() {
+        let diff = x as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = if diff < 0 { 1 } else { 0 };
+        result.push((diff & ((1 << BITS) - 1)) as u32);
     }
+    debug_assert!(borrow == 0, "sub_digits: вычитаемое больше уменьшаемого");
+    result
+}
 
-    fn trim(&mut self) {   // Приватный метод trim, который удаляет нулевые элементы из конца вектора digits структуры
-        while let Some(&0) = self.digits.last() {
-            self.digits.pop();
+// Сложение модулей на месте: |a| += |b|
+fn add_digits_assign(a: &mut Vec<u32>, b: &[u32]) {
+    if a.len() < b.len() {
+        a.resize(b.len(), 0);
+    }
+    let mut carry = 0u64;
+    for (i, d) in a.iter_mut().enumerate() {
+        if i >= b.len() && carry == 0 {
+            break; // Дальше разряды a не меняются
         }
-        if self.digits.is_empty() {    // Если вектор digits пустой, то добавляется элемент 0
-            self.digits.push(0);
+        let sum = *d as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        *d = sum as u32;
+        carry = sum >> BITS;
+    }
+    if carry > 0 {
+        a.push(carry as u32);
+    }
+}
+
+// Вычитание модулей на месте: |a| -= |b|, требуется |a| >= |b|
+fn sub_digits_assign(a: &mut [u32], b: &[u32]) {
+    let mut borrow = 0i64;
+    for (i, d) in a.iter_mut().enumerate() {
+        if i >= b.len() && borrow == 0 {
+            break;
         }
+        let diff = *d as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = if diff < 0 { 1 } else { 0 };
+        *d = (diff & ((1 << BITS) - 1)) as u32;
     }
+    debug_assert!(borrow == 0, "sub_digits_assign: вычитаемое больше уменьшаемого");
+}
 
-    pub fn new() -> MyBigInt {
-    MyBigInt {
-        digits: Vec::new(), // создаем новый вектор для хранения цифр
-        sign: Sign::Positive, // устанавливаем знак на положительный
-        other: BigNumber { // создаем структуру BigNumber, связанную с MyBigInt
-            data: vec![],
-            hex: String::new(),
-        },
+// Смена знака в дополнительном коде на месте: a = !a + 1
+fn negate_digits_twos(a: &mut [u32]) {
+    let mut carry = 1u64;
+    for d in a.iter_mut() {
+        let t = (!*d) as u64 + carry;
+        *d = t as u32;
+        carry = t >> BITS;
     }
 }
 
-// побитовая инверсия
-pub fn inv(&mut self) {
-    for i in 0..self.digits.len() { // проходимся по каждому элементу вектора digits
-        self.digits[i] = !self.digits[i]; // инвертируем значение каждого элемента вектора digits
+// Прибавляет x, сдвинутое на offset разрядов, к acc (acc должен вмещать результат)
+fn add_digits_at(acc: &mut [u32], x: &[u32], offset: usize) {
+    let mut carry = 0u64;
+    let mut i = 0;
+    while i < x.len() || carry > 0 {
+        let sum = acc[offset + i] as u64 + *x.get(i).unwrap_or(&0) as u64 + carry;
+        acc[offset + i] = sum as u32;
+        carry = sum >> BITS;
+        i += 1;
     }
 }
 
-// побитовое исключительное ИЛИ
-pub fn xor(&self, other: &MyBigInt) -> MyBigInt {
-    let mut result = MyBigInt::default(); // создаем новый экземпляр MyBigInt с пустым вектором digits и положительным знаком
-    let len = self.digits.len().max(other.digits.len()); // определяем длину, на которую необходимо продлить вектор digits в случае, если одно из чисел короче другого
+// Умножение модулей "в столбик" за O(n·m) с 64-битными аккумуляторами
+fn mul_schoolbook(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        if x == 0 {
+            continue;
+        }
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            // x * y + result + carry < 2^64, поэтому переполнения нет
+            let t = x as u64 * y as u64 + result[i + j] as u64 + carry;
+            result[i + j] = t as u32;
+            carry = t >> BITS;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+    result
+}
 
-    for i in 0..len { // проходимся по каждому элементу вектора digits в диапазоне от 0 до len
-        let a = *self.digits.get(i).unwrap_or(&0); // получаем значение i-го элемента вектора digits экземпляра self
-        let b = *other.digits.get(i).unwrap_or(&0); // получаем значение i-го элемента вектора digits экземпляра other
-        result.digits.push(a ^ b); // выполняем побитовое исключающее ИЛИ между a и b и добавляем результат в вектор digits результата
+// Умножение модулей методом Карацубы: три рекурсивных умножения половин вместо четырех
+fn mul_karatsuba(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return mul_schoolbook(a, b);
     }
 
-    // добавляем оставшиеся элементы более длинного числа в вектор digits результата
-    for i in len..self.digits.len() {
-        result.digits.push(self.digits[i]);
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let half = a.len() / 2;
+    let mut result = vec![0u32; a.len() + b.len() + 1];
+
+    if b.len() <= half {
+        // Сильно несбалансированные множители: делим только длинный a = a1·B^half + a0
+        let low = mul_karatsuba(&a[..half], b);
+        let high = mul_karatsuba(&a[half..], b);
+        add_digits_at(&mut result, &low, 0);
+        add_digits_at(&mut result, &high, half);
+    } else {
+        // a = a1·B^half + a0, b = b1·B^half + b0
+        let (a0, a1) = a.split_at(half);
+        let (b0, b1) = b.split_at(half);
+        let z0 = mul_karatsuba(a0, b0);
+        let z2 = mul_karatsuba(a1, b1);
+        let z1 = mul_karatsuba(&add_digits(a0, a1), &add_digits(b0, b1));
+        let middle = sub_digits(&sub_digits(&z1, &z0), &z2); // a0·b1 + a1·b0
+
+        result.resize(result.len().max(half + middle.len()).max(2 * half + z2.len()) + 1, 0);
+        add_digits_at(&mut result, &z0, 0);
+        add_digits_at(&mut result, &middle, half);
+        add_digits_at(&mut result, &z2, 2 * half);
     }
 
-    for i in len..other.digits.len() {
-        result.digits.push(other.digits[i]);
+    result.truncate(a.len() + b.len()); // Старшие разряды сверх a.len() + b.len() заведомо нулевые
+    result
+}
+
+// Умножение модулей с выбором алгоритма по размеру операндов
+fn mul_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let a = &a[..a.len() - a.iter().rev().take_while(|&&d| d == 0).count()];
+    let b = &b[..b.len() - b.iter().rev().take_while(|&&d| d == 0).count()];
+    if a.is_empty() || b.is_empty() {
+        return vec![0];
     }
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        mul_schoolbook(a, b)
+    } else {
+        mul_karatsuba(a, b)
+    }
+}
 
-    result.trim(); // удаляем ведущие нули из вектора digits результата
-    result // возвращаем экземпляр MyBigInt с новым вектором digits
+// Сдвиг модуля влево на s < 32 бит; результат всегда на один разряд длиннее a
+fn shl_digits_small(a: &[u32], s: u32) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0u32;
+    for &x in a {
+        result.push((x << s) | carry);
+        carry = if s == 0 { 0 } else { x >> (BITS - s) };
+    }
+    result.push(carry);
+    result
 }
 
-    // побитовое ИЛИ
-    pub fn or(&self, other: &MyBigInt) -> MyBigInt {
-        let mut result = MyBigInt::default();
-        let len = self.digits.len().max(other.digits.len()); // Вычисляем длину, которая будет использоваться при итерировании цикла
+// Деление модулей с остатком (алгоритм D Кнута), b не должно быть нулем
+fn div_rem_digits(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let a = &a[..a.len() - a.iter().rev().take_while(|&&d| d == 0).count()];
+    let b = &b[..b.len() - b.iter().rev().take_while(|&&d| d == 0).count()];
+    assert!(!b.is_empty(), "attempt to divide by zero");
+
+    if cmp_digits(a, b) == Ordering::Less {
+        return (vec![0], if a.is_empty() { vec![0] } else { a.to_vec() });
+    }
+
+    // Делитель из одного разряда: обычное деление "уголком"
+    if b.len() == 1 {
+        let d = b[0] as u64;
+        let mut quotient = vec![0u32; a.len()];
+        let mut rem = 0u64;
+        for i in (0..a.len()).rev() {
+            let cur = (rem << BITS) | a[i] as u64;
+            quotient[i] = (cur / d) as u32;
+            rem = cur % d;
+        }
+        return (quotient, vec![rem as u32]);
+    }
+
+    // Нормализация: сдвигаем оба числа так, чтобы старший бит делителя был равен 1
+    let n = b.len();
+    let m = a.len() - n;
+    let s = b[n - 1].leading_zeros();
+    let v = shl_digits_small(b, s);
+    let mut u = shl_digits_small(a, s);
+    let mut quotient = vec![0u32; m + 1];
+
+    for j in (0..=m).rev() {
+        // Оценка очередной цифры частного по двум старшим разрядам остатка
+        let num = ((u[j + n] as u64) << BITS) | u[j + n - 1] as u64;
+        let mut qhat = num / v[n - 1] as u64;
+        let mut rhat = num % v[n - 1] as u64;
+        while qhat >> BITS != 0 || qhat * v[n - 2] as u64 > ((rhat << BITS) | u[j + n - 2] as u64) {
+            qhat -= 1;
+            rhat += v[n - 1] as u64;
+            if rhat >> BITS != 0 {
+                break;
+            }
+        }
 
-        // Цикл проходится по каждому биту чисел, выполняет операцию "ИЛИ" и записывает результат в новый MyBigInt
-        for i in 0..len {
-          let a = *self.digits.get(i).unwrap_or(&0); // Берем i-й элемент из вектора, если его нет, то используем 0
-          let b = *other.digits.get(i).unwrap_or(&0); // Аналогично для второго числа
-          result.digits.push(a | b); // Добавляем результат операции "ИЛИ" в новый вектор
-         }
+        // Вычитаем qhat * v из текущего окна остатка
+        let mut k = 0i64;
+        for i in 0..n {
+            let p = qhat * v[i] as u64;
+            let t = u[i + j] as i64 - k - (p & 0xFFFF_FFFF) as i64;
+            u[i + j] = t as u32;
+            k = (p >> BITS) as i64 - (t >> BITS);
+        }
+        let t = u[j + n] as i64 - k;
+        u[j + n] = t as u32;
 
-        result.trim(); // Отрезаем старшие нули от результата
-        result // Возвращаем результат
+        // Оценка оказалась на единицу больше: возвращаем делитель обратно
+        if t < 0 {
+            qhat -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let sum = u[i + j] as u64 + v[i] as u64 + carry;
+                u[i + j] = sum as u32;
+                carry = sum >> BITS;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u32);
+        }
+        quotient[j] = qhat as u32;
+    }
 
+    // Остаток — младшие n разрядов u, сдвинутые обратно на s бит
+    let mut remainder = vec![0u32; n];
+    for i in 0..n {
+        remainder[i] = if s == 0 { u[i] } else { (u[i] >> s) | (u[i + 1] << (BITS - s)) };
     }
+    (quotient, remainder)
+}
 
-    // побитовое И
-    pub fn and(&self, other: &MyBigInt) -> MyBigInt {
-        let mut result = MyBigInt::default();
-        let len = self.digits.len().max(other.digits.len()); / Вычисляем длину, которая будет использоваться при итерировании цикла
+// -m^(-1) mod 2^32 для нечетного m0 (младшего разряда модуля), метод Ньютона: каждая итерация удваивает число верных бит
+fn montgomery_inverse(m0: u32) -> u32 {
+    let mut inv = 1u32;
+    for _ in 0..5 {
+        inv = inv.wrapping_mul(2u32.wrapping_sub(m0.wrapping_mul(inv)));
+    }
+    inv.wrapping_neg()
+}
 
-        // Цикл проходится по каждому биту чисел, выполняет операцию "И" и записывает результат в новый MyBigInt
-          for i in 0..len {
-              let a = *self.digits.get(i).unwrap_or(&0); // Берем i-й элемент из вектора, если его нет, то используем 0
-              let b = *other.digits.get(i).unwrap_or(&0); // Аналогично для второго числа
-              result.digits.push(a & b); // Добавляем результат операции "И" в новый вектор
-          }
+// Умножение Монтгомери (CIOS): out = a·b·R^(-1) mod m, где R = 2^(32·n), n = m.len().
+// a, b, out содержат ровно n разрядов и меньше m; t — рабочий буфер из n + 2 разрядов.
+fn montgomery_mul(out: &mut [u32], a: &[u32], b: &[u32], m: &[u32], m_inv: u32, t: &mut [u32]) {
+    let n = m.len();
+    t.iter_mut().for_each(|x| *x = 0);
 
-        result.trim(); // Отрезаем старшие нули от результата
-        result // Возвращаем результат
+    for &ai in a.iter().take(n) {
+        // t += a[i]·b
+        let mut carry = 0u64;
+        for j in 0..n {
+            let s = t[j] as u64 + ai as u64 * b[j] as u64 + carry;
+            t[j] = s as u32;
+            carry = s >> BITS;
+        }
+        let s = t[n] as u64 + carry;
+        t[n] = s as u32;
+        t[n + 1] = (s >> BITS) as u32;
 
+        // t = (t + u·m) / 2^32, где u подобрано так, чтобы младший разряд обнулился
+        let u = t[0].wrapping_mul(m_inv);
+        let s = t[0] as u64 + u as u64 * m[0] as u64;
+        let mut carry = s >> BITS;
+        for j in 1..n {
+            let s = t[j] as u64 + u as u64 * m[j] as u64 + carry;
+            t[j - 1] = s as u32;
+            carry = s >> BITS;
+        }
+        let s = t[n] as u64 + carry;
+        t[n - 1] = s as u32;
+        t[n] = t[n + 1] + (s >> BITS) as u32;
+        t[n + 1] = 0;
     }
 
-    // сдвиг в право на n битов
-    pub fn shift_r(&self, n: u32) -> MyBigInt {
-        let mut result = MyBigInt::default();
-        let mut carry = 0u32;
+    // Результат меньше 2m: при необходимости вычитаем модуль один раз
+    if t[n] != 0 || cmp_digits(&t[..n], m) != Ordering::Less {
+        let mut borrow = 0i64;
+        for j in 0..n {
+            let diff = t[j] as i64 - m[j] as i64 - borrow;
+            borrow = if diff < 0 { 1 } else { 0 };
+            t[j] = diff as u32;
+        }
+    }
+    out.copy_from_slice(&t[..n]);
+}
+
+// Возведение в квадрат по Монтгомери: out = a²·R^(-1) mod m.
+// Полный квадрат считается с учетом симметрии (a_i·a_j при i < j один раз, затем удвоение),
+// после чего выполняется редукция REDC; t — рабочий буфер из 2n + 1 разрядов.
+fn montgomery_sqr(out: &mut [u32], a: &[u32], m: &[u32], m_inv: u32, t: &mut [u32]) {
+    let n = m.len();
+    t.iter_mut().for_each(|x| *x = 0);
+
+    // Перекрестные произведения a_i·a_j, i < j
+    for i in 0..n {
+        let mut carry = 0u64;
+        for j in i + 1..n {
+            let s = t[i + j] as u64 + a[i] as u64 * a[j] as u64 + carry;
+            t[i + j] = s as u32;
+            carry = s >> BITS;
+        }
+        t[i + n] = carry as u32;
+    }
+
+    // Удваиваем и добавляем квадраты a_i²
+    let mut shifted_out = 0u32;
+    for x in t.iter_mut().take(2 * n) {
+        let v = *x;
+        *x = (v << 1) | shifted_out;
+        shifted_out = v >> (BITS - 1);
+    }
+    let mut carry = 0u64;
+    for i in 0..n {
+        let sq = a[i] as u64 * a[i] as u64;
+        let s = t[2 * i] as u64 + (sq & 0xFFFF_FFFF) + carry;
+        t[2 * i] = s as u32;
+        let s = t[2 * i + 1] as u64 + (sq >> BITS) + (s >> BITS);
+        t[2 * i + 1] = s as u32;
+        carry = s >> BITS;
+    }
 
-        // Проходим по всем цифрам числа
-        for i in 0..self.digits.len() {
-            let x = u32::from(self.digits[i]); // конвертируем в u32
-            // Сдвигаем текущую цифру вправо на n бит и применяем битовое ИЛИ с предыдущим carry
-            let y = (x >> n) | carry;
-            // Сохраняем остаток от сдвига текущей цифры в carry
-            carry = x << (32 - n);
-            // Добавляем новую цифру к результату
-            result.digits.push(y as u32);
+    // REDC: n раз обнуляем младший разряд, прибавляя u·m
+    for i in 0..n {
+        let u = t[i].wrapping_mul(m_inv);
+        let mut carry = 0u64;
+        for j in 0..n {
+            let s = t[i + j] as u64 + u as u64 * m[j] as u64 + carry;
+            t[i + j] = s as u32;
+            carry = s >> BITS;
+        }
+        let mut k = i + n;
+        while carry > 0 {
+            let s = t[k] as u64 + carry;
+            t[k] = s as u32;
+            carry = s >> BITS;
+            k += 1;
         }
+    }
 
-        // Если после цикла остался непустой carry, добавляем его как новую цифру
-        if carry != 0 {
-            result.digits.push(carry as u32);
+    let r = &mut t[n..];
+    if r[n] != 0 || cmp_digits(&r[..n], m) != Ordering::Less {
+        let mut borrow = 0i64;
+        for j in 0..n {
+            let diff = r[j] as i64 - m[j] as i64 - borrow;
+            borrow = if diff < 0 { 1 } else { 0 };
+            r[j] = diff as u32;
         }
+    }
+    out.copy_from_slice(&r[..n]);
+}
 
-        // Обрезаем лидирующие нули
-        result.trim();
+// Возведение в степень скользящим окном: base^exp.
+// step(acc, Some(x)) должна выполнять acc = acc·x, а step(acc, None) — acc = acc², в выбранной арифметике;
+// так реализация умножения может переиспользовать свои буферы между шагами.
+fn sliding_window_pow<T: Clone>(base: &T, one: T, exp: &MyBigInt, mut step: impl FnMut(&mut T, Option<&T>)) -> T {
+    let bits = exp.bits();
+    let window = match bits {
+        0..=23 => 1,
+        24..=79 => 3,
+        80..=239 => 4,
+        240..=671 => 5,
+        _ => 6,
+    };
 
-        // Возвращаем результат
-        result
+    // Таблица нечетных степеней: table[k] = base^(2k + 1)
+    let mut square = base.clone();
+    step(&mut square, None);
+    let mut table = vec![base.clone()];
+    for k in 1..(1usize << (window - 1)) {
+        let mut next = table[k - 1].clone();
+        step(&mut next, Some(&square));
+        table.push(next);
+    }
+
+    let mut result = one;
+    let mut i = bits;
+    while i > 0 {
+        if !exp.test_bit(i - 1) {
+            step(&mut result, None);
+            i -= 1;
+            continue;
+        }
+        // Окно из не более чем window бит, заканчивающееся единицей
+        let mut len = window.min(i);
+        while !exp.test_bit(i - len) {
+            len -= 1;
+        }
+        let mut value = 0usize;
+        for b in (i - len..i).rev() {
+            value = (value << 1) | exp.test_bit(b) as usize;
+            step(&mut result, None);
+        }
+        step(&mut result, Some(&table[value >> 1]));
+        i -= len;
+    }
+    result
+}
+
+// Умножение модуля на один разряд на месте
+fn mul_digits_small_assign(a: &mut Vec<u32>, m: u32) {
+    let mut carry = 0u64;
+    for d in a.iter_mut() {
+        let t = *d as u64 * m as u64 + carry;
+        *d = t as u32;
+        carry = t >> BITS;
+    }
+    if carry > 0 {
+        a.push(carry as u32);
     }
+}
 
+// Деление модуля на один разряд на месте, возвращает остаток
+fn div_rem_digits_small(a: &mut [u32], d: u32) -> u32 {
+    let mut rem = 0u64;
+    for x in a.iter_mut().rev() {
+        let cur = (rem << BITS) | *x as u64;
+        *x = (cur / d as u64) as u32;
+        rem = cur % d as u64;
+    }
+    rem as u32
+}
 
-    // сдвиг в лево на n битов
-    pub fn shift_l(&self, n: u32) -> MyBigInt {
-        let mut result = MyBigInt::default();
-        let mut carry = 0;
+// Наибольшая степень radix, помещающаяся в один разряд: (radix^k, k)
+fn radix_chunk(radix: u32) -> (u32, usize) {
+    let mut base = radix;
+    let mut k = 1;
+    while let Some(next) = base.checked_mul(radix) {
+        base = next;
+        k += 1;
+    }
+    (base, k)
+}
 
-        // Итерируем цифры числа в обратном порядке
-        for i in (0..self.digits.len()).rev() {
-            let x = self.digits[i];
-            // Сдвигаем текущую цифру влево на n бит и применяем битовое ИЛИ с предыдущим carry
-            let y = (x << n) | carry;
-            // Сохраняем остаток от сдвига текущей цифры в carry
-            carry = x >> (BITS - n);
-            // Добавляем новую цифру к результату в начало вектора
-            result.digits.insert(0, y);
+// Таблица малых простых решетом Эратосфена на этапе компиляции
+const fn small_primes() -> [u32; 564] {
+    let mut composite = [false; SMALL_PRIME_LIMIT];
+    let mut primes = [0u32; 564];
+    let mut count = 0;
+    let mut i = 2;
+    while i < SMALL_PRIME_LIMIT {
+        if !composite[i] {
+            primes[count] = i as u32;
+            count += 1;
+            let mut j = i * i;
+            while j < SMALL_PRIME_LIMIT {
+                composite[j] = true;
+                j += i;
+            }
         }
+        i += 1;
+    }
+    primes
+}
+
+// Источник случайных чисел для генерации больших чисел (по образцу rand_core::RngCore)
+pub trait RngCore {
+    fn next_u32(&mut self) -> u32;
+
+    fn next_u64(&mut self) -> u64 {
+        (self.next_u32() as u64) | ((self.next_u32() as u64) << 32)
+    }
 
-        // Если после цикла остался непустой carry, добавляем его как новую цифру
-        if carry != 0 {
-            result.digits.insert(0, carry);
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(4) {
+            let bytes = self.next_u32().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
         }
+    }
+}
 
-        // Обрезаем лидирующие нули
-        result.trim();
+// Детерминированный генератор SplitMix64: одинаковое зерно дает одинаковую последовательность.
+// Подходит для тестов и случайных оснований Миллера–Рабина, но не для ключей — он не криптостойкий.
+#[derive(Clone, Debug)]
+pub struct SplitMix64 {
+    state: u64,
+}
 
-        // Возвращаем результат
-        result
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
     }
 
-    // ADD 
-    pub fn add(&self, other: &MyBigInt) -> MyBigInt {
-        // Создаем новый MyBigInt результат.
-        let mut result = MyBigInt::default();
-        // Определяем максимальную длину двух чисел для определения количества итераций в цикле.
-        let len = self.digits.len().max(other.digits.len());
-
-        // carry - это переменная для хранения переноса из предыдущего разряда при сложении.
-        let mut carry = 0;
-        // Цикл, который проходит по всем разрядам двух чисел и складывает их.
-        for i in 0..len {
-            // Получаем значение i-го разряда в каждом из чисел.
-            let a = *self.digits.get(i).unwrap_or(&0);
-            let b = *other.digits.get(i).unwrap_or(&0);
-            // Складываем значения разрядов, а также значение переноса из предыдущего разряда.
-            let sum = a as u64 + b as u64 + carry as u64;
-            // Вычисляем новый перенос.
-            carry = (sum >> BITS) as u32;
-            // Добавляем остаток от суммы разрядов (без учета переноса) в результат.
-            result.digits.push((sum & ((1 << BITS) - 1)) as u32);
-        }
-
-        // Если после последнего разряда остался перенос, добавляем его в результат.
-        if carry > 0 {
-            result.digits.push(carry);
-        }
-
-        // Убираем ведущие нули в результате.
-        result.trim();
-        // Вычисляем знак результата.
-        result.sign = if self.sign == other.sign {
-            self.sign.clone()
-        } else if self.abs() > other.abs() {
-            self.sign.clone()
-        } else {
-            other.sign.clone()
-        };
+    // Зерно из RandomState, который std заполняет случайными ключами при старте процесса
+    fn from_random_state() -> Self {
+        use std::hash::BuildHasher;
+        SplitMix64::new(RandomState::new().hash_one(0x9E37_79B9_7F4A_7C15u64))
+    }
+}
 
-        // Возвращаем результат.
-        result
+impl RngCore for SplitMix64 {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
     }
 
-    // SUB 
-    pub fn sub(&self, other: &MyBigInt) -> MyBigInt { // Определение метода sub с двумя параметрами типа MyBigInt и возвратом MyBigInt
-        let mut result = MyBigInt::default(); // Создание нового экземпляра MyBigInt с помощью конструктора MyBigInt::default()
-        let len = self.digits.len().max(other.digits.len()); // Находим максимальную длину между digits текущего экземпляра и digits другого экземпляра
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
 
-        let mut borrow = 0; // Инициализируем значение "заема" как 0
-            for i in 0..len { // Итерируем от 0 до len
-                let a = *self.digits.get(i).unwrap_or(&0); // Получаем цифру текущего экземпляра MyBigInt по индексу i, или 0, если такого индекса нет.
-                let b = *other.digits.get(i).unwrap_or(&0); // Получаем цифру другого экземпляра MyBigInt по индексу i, или 0, если такого индекса нет.
-                let diff = a as i64 - b as i64 - borrow as i64; // Вычисляем разность между цифрами с учетом займа.
-                borrow = if diff < 0 { 1 } else { 0 }; // Если разность меньше нуля, то значение займа увеличивается на 1.
-                result.digits.push((diff & ((1 << BITS) - 1)) as u32); // Записываем последнюю цифру разности в digits результата.
+// Сильный тест Миллера–Рабина для нечетного n > 3 по основаниям из bases (каждое из [2, n - 2]); ctx построен для n
+fn miller_rabin(ctx: &MontgomeryCtx, n: &MyBigInt, bases: impl Iterator<Item = MyBigInt>) -> bool {
+    let n_minus_1 = n - 1u32;
+    let s = n_minus_1.trailing_zeros().unwrap();
+    let d = &n_minus_1 >> s;
+    let minus_one = ctx.to_montgomery(&n_minus_1);
+    let mut y = vec![0; ctx.limbs()];
+    let mut scratch = vec![0; ctx.scratch_len()];
+
+    'bases: for a in bases {
+        let mut x = ctx.pow(&ctx.to_montgomery(&a), &d);
+        if x == ctx.one() || x == minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            ctx.sqr(&mut y, &x, &mut scratch);
+            std::mem::swap(&mut x, &mut y);
+            if x == minus_one {
+                continue 'bases;
             }
+            if x == ctx.one() {
+                return false; // Нетривиальный квадратный корень из единицы
+            }
+        }
+        return false;
+    }
+    true
+}
+
+// Криптостойкий генератор операционной системы: байты читаются из /dev/urandom.
+// Файл открывается один раз в new() и читается без буфера: каждый fill_bytes забирает из ядра ровно
+// запрошенные байты, поэтому будущие случайные значения не хранятся в памяти процесса и не
+// дублируются в дочернем процессе после fork. Большое число заполняется одним read_exact.
+// Доступен только на unix-системах; на других платформах типа нет, и нужен собственный RngCore поверх API ОС.
+// Ошибка чтения считается фатальной — продолжать с предсказуемыми числами нельзя.
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct OsRng {
+    source: std::fs::File,
+}
+
+#[cfg(unix)]
+impl OsRng {
+    pub fn new() -> std::io::Result<OsRng> {
+        Ok(OsRng { source: std::fs::File::open("/dev/urandom")? })
+    }
+}
+
+#[cfg(unix)]
+impl RngCore for OsRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        use std::io::Read;
+        self.source.read_exact(dest).expect("OsRng: failed to read /dev/urandom");
+    }
+}
+
+// Решето по нечетным малым простым для перебора кандидатов start, start + 2, start + 4, ...:
+// остатки start хранятся один раз, и проверка кандидата не требует длинной арифметики
+struct SmallPrimeSieve {
+    residues: Vec<u32>, // start mod p для SMALL_PRIMES[1..]
+}
+
+impl SmallPrimeSieve {
+    fn new(start: &MyBigInt) -> Self {
+        SmallPrimeSieve { residues: SMALL_PRIMES[1..].iter().map(|&p| start.rem_u32(p)).collect() }
+    }
+
+    // start + delta не делится ни на одно нечетное малое простое
+    fn is_coprime(&self, delta: u64) -> bool {
+        SMALL_PRIMES[1..].iter().zip(&self.residues).all(|(&p, &r)| !(r as u64 + delta).is_multiple_of(p as u64))
+    }
+
+    // И start + delta, и 2·(start + delta) + 1 не делятся ни на одно нечетное малое простое
+    fn is_safe_coprime(&self, delta: u64) -> bool {
+        SMALL_PRIMES[1..].iter().zip(&self.residues).all(|(&p, &r)| {
+            let x = (r as u64 + delta) % p as u64;
+            x != 0 && x != (p as u64 - 1) / 2
+        })
+    }
+}
+
+// Ошибка деления на ноль
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DivisionByZero;
+
+impl fmt::Display for DivisionByZero {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "attempt to divide by zero")
+    }
+}
+
+impl std::error::Error for DivisionByZero {}
+
+// Ошибка преобразования большого числа в примитивный тип, в который оно не помещается
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromBigIntError;
+
+impl fmt::Display for TryFromBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "out of range integral type conversion attempted")
+    }
+}
+
+impl std::error::Error for TryFromBigIntError {}
+
+// Ошибка кодирования в байтовую строку фиксированной длины: число в нее не помещается (I2OSP "integer too large")
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntegerTooLargeError;
+
+impl fmt::Display for IntegerTooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "integer too large for the requested byte length")
+    }
+}
 
-            if borrow > 0 { // Если значение займа больше нуля, то добавляем цифру 0 в digits результата.
-                result.digits.push(0);
+impl std::error::Error for IntegerTooLargeError {}
+
+// Ошибка обращения по модулю: gcd(a, m) != 1, сам gcd возвращается для диагностики (например, найденный делитель модуля)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotInvertible {
+    pub gcd: MyBigInt,
+}
+
+impl fmt::Display for NotInvertible {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value is not invertible: gcd with modulus is {}", self.gcd)
+    }
+}
+
+impl std::error::Error for NotInvertible {}
+
+// Ошибка разбора строкового представления большого числа
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBigIntError {
+    Empty,                                         // Строка не содержит ни одной цифры
+    InvalidDigit { position: usize, found: char }, // Недопустимый символ в позиции position (в байтах)
+    InvalidSign { position: usize },               // Знак '+' или '-' стоит не в начале строки
+}
+
+impl fmt::Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBigIntError::Empty => write!(f, "cannot parse integer from empty string"),
+            ParseBigIntError::InvalidDigit { position, found } => {
+                write!(f, "invalid digit {:?} at position {}", found, position)
+            }
+            ParseBigIntError::InvalidSign { position } => {
+                write!(f, "unexpected sign at position {}", position)
             }
+        }
+    }
+}
 
-            result.trim(); // Удаляем нули в начале digits результата.
-            result.sign = if self.abs() >= other.abs() { // Определяем знак результата на основе значений self и other, используя метод abs() (абсолютное значение).
-                self.sign.clone() // Если self.abs() >= other.abs(), то знак результата равен знаку self.
-            } else {
-                self.sign.clone().swap() // Иначе, знак результата равен обратному знаку self.
-            };
+impl std::error::Error for ParseBigIntError {}
 
-        result // Возвращаем результат.
-      }
+#[derive(Clone)]
+pub struct MyBigInt {
+    sign: Sign,  // Знак числа
+    digits: Vec<u32>,  // Вектор цифр числа в порядке от младших разрядов к старшим
+    other: BigNumber,  // Вспомогательное поле для хранения числа в виде строки шестнадцатеричных цифр
+}
 
+impl Default for MyBigInt {
+    fn default() -> Self {
+        Self {
+            digits: vec![0],  // По умолчанию число равно нулю
+            sign: Sign::Positive, // По умолчанию число положительное
+            other: BigNumber {
+                data: vec![],
+                hex: String::new(),
+            },
+        }
+    }
+}
 
-    // MOD 
-    pub fn modulus(&self, other: &MyBigInt) -> MyBigInt {
-        let mut dividend = self.abs(); // Получаем модуль делимого
-        let divisor = other.abs(); // Получаем модуль делителя
+impl MyBigInt {  // Определение структуры MyBigInt
+    pub fn set_hex(&mut self, hex: &str) {  // Публичный метод set_hex, который разбирает строку hex (с необязательным знаком '-') в digits и sign
+        self.try_set_hex(hex).expect("set_hex: некорректная шестнадцатеричная строка");
+    }
 
-        let mut quotient = MyBigInt::default(); // Инициализируем частное нулем
-        let mut remainder = MyBigInt::default(); // Инициализируем остаток нулем
+    // Вариант set_hex, который вместо паники возвращает ошибку разбора
+    pub fn try_set_hex(&mut self, hex: &str) -> Result<(), ParseBigIntError> {
+        let parsed = MyBigInt::from_str_radix(hex, 16)?;
+        self.sign = parsed.sign;
+        self.digits = parsed.digits;
+        self.other.set_hex(self.get_hex().trim_start_matches('-')); // Обновляем вспомогательное представление модуля числа
+        Ok(())
+    }
 
-        // Проходим по всем цифрам делимого в обратном порядке
-        for i in (0..dividend.digits.len()).rev() {
-            remainder.digits.insert(0, dividend.digits[i]); // Добавляем текущую цифру в начало остатка
+    // Разбор строки в системе счисления radix (2..=36).
+    // Допускаются знак '+' или '-' в начале, префикс "0x" для radix = 16 и разделители '_' между цифрами
+    // (по одному: '_' в начале, в конце или два подряд — ошибка InvalidDigit).
+    pub fn from_str_radix(src: &str, radix: u32) -> Result<MyBigInt, ParseBigIntError> {
+        assert!((2..=36).contains(&radix), "from_str_radix: radix must lie in the range [2, 36]");
 
-            let mut x = MyBigInt::from(0); // Инициализируем переменную-множитель нулем
-            let mut left = 0; // Инициализируем левую границу диапазона значений множителя
-            let mut right = (1 << BITS) - 1; // Инициализируем правую границу диапазона значений множителя
+        let mut result = MyBigInt::default();
+        let mut rest = src;
+        let mut offset = 0; // Смещение rest относительно начала src
 
-            // Бинарный поиск множителя, который является наибольшим числом, удовлетворяющим условию product <= remainder
-            while left <= right {
-                let mid = (left + right) / 2; // Вычисляем среднее значение между левой и правой границей
-                x.digits = vec![mid]; // Задаем множитель x
+        if let Some(stripped) = rest.strip_prefix('-') {
+            result.sign = Sign::Negative;
+            rest = stripped;
+            offset += 1;
+        } else if let Some(stripped) = rest.strip_prefix('+') {
+            rest = stripped;
+            offset += 1;
+        }
 
-                let product = divisor.mul(&x); // Умножаем делитель на множитель x
+        if radix == 16 {
+            if let Some(stripped) = rest.strip_prefix("0x").or_else(|| rest.strip_prefix("0X")) {
+                rest = stripped;
+                offset += 2;
+            }
+        }
 
-                if product <= remainder { // Если произведение меньше или равно остатку
-                    quotient = quotient.mul(&MyBigInt::from((1 << BITS))); // Увеличиваем частное на 2^BITS
-                    quotient = quotient.add(&x); // Добавляем к частному множитель x
-                    remainder = remainder.sub(&product); // Вычитаем из остатка произведение делителя на множитель x
-                    break; // Прекращаем бинарный поиск
-                } else {
-                    right = mid - 1; // Смещаем правую границу диапазона значений множителя
+        // Собираем значения цифр от старших к младшим, проверяя каждый символ
+        let mut values = Vec::with_capacity(rest.len());
+        let mut chars = rest.char_indices().peekable();
+        let mut after_digit = false; // Предыдущий символ — цифра
+        while let Some((i, c)) = chars.next() {
+            let before_digit = chars.peek().is_some_and(|&(_, next)| next.is_digit(radix));
+            match c {
+                '_' if after_digit && before_digit => {
+                    after_digit = false;
+                    continue;
                 }
+                '+' | '-' => return Err(ParseBigIntError::InvalidSign { position: offset + i }),
+                _ => match c.to_digit(radix) {
+                    Some(v) => {
+                        values.push(v);
+                        after_digit = true;
+                    }
+                    None => return Err(ParseBigIntError::InvalidDigit { position: offset + i, found: c }),
+                },
             }
         }
+        if values.is_empty() {
+            return Err(ParseBigIntError::Empty);
+        }
 
-        quotient.trim(); // Удаляем ведущие нули из частного
-        quotient.sign = if self.sign == other.sign {
-            self.sign.clone() // Если знаки делимого и делителя совпадают, то знак частного будет положительным
+        result.digits = if radix.is_power_of_two() {
+            // Для степеней двойки просто упаковываем биты цифр в разряды, начиная с младших
+            let bits_per_digit = radix.trailing_zeros();
+            let mut digits = vec![0u32; (values.len() * bits_per_digit as usize) / BITS as usize + 1];
+            let mut pos = 0usize;
+            for &v in values.iter().rev() {
+                let limb = pos / BITS as usize;
+                let shift = (pos % BITS as usize) as u32;
+                digits[limb] |= v << shift;
+                if shift + bits_per_digit > BITS {
+                    digits[limb + 1] |= v >> (BITS - shift);
+                }
+                pos += bits_per_digit as usize;
+            }
+            digits
         } else {
-            Sign::Negative // Иначе знак частного будет отрицательным
+            // Иначе собираем число методом "разделяй и властвуй" (для коротких строк — по схеме Горнера)
+            let mut powers = Vec::new();
+            MyBigInt::from_radix_values(&values, radix, &mut powers).digits
+        };
+
+        result.trim();
+        Ok(result)
+    }
+
+    pub fn get_hex(&self) -> String { // Публичный метод get_hex, который строит шестнадцатеричную строку по вектору digits
+        let mut limbs = self.digits.iter().rev().skip_while(|&&d| d == 0); // Пропускаем старшие нулевые разряды
+        let first = match limbs.next() {
+            Some(first) => first,
+            None => return String::from("0"),
         };
 
-        quotient // Возвращаем частное
+        let mut hex = String::new();
+        if let Sign::Negative = self.sign {
+            hex.push('-');
+        }
+        hex.push_str(&format!("{:X}", first)); // Старший разряд выводим без ведущих нулей
+        for d in limbs {
+            hex.push_str(&format!("{:08X}", d)); // Остальные разряды дополняем нулями до 8 символов
+        }
+        hex
+    }
+
+    fn trim(&mut self) {   // Приватный метод trim, который удаляет нулевые элементы из конца вектора digits структуры
+        while let Some(&0) = self.digits.last() {
+            self.digits.pop();
+        }
+        if self.digits.is_empty() {    // Если вектор digits пустой, то добавляется элемент 0
+            self.digits.push(0);
+        }
+        if self.digits == [0] {    // Ноль всегда хранится со знаком плюс, чтобы не было "отрицательного нуля"
+            self.sign = Sign::Positive;
+        }
+    }
+
+    pub fn new() -> MyBigInt {
+    MyBigInt {
+        digits: Vec::new(), // создаем новый вектор для хранения цифр
+        sign: Sign::Positive, // устанавливаем знак на положительный
+        other: BigNumber { // создаем структуру BigNumber, связанную с MyBigInt
+            data: vec![],
+            hex: String::new(),
+        },
+    }
+}
+
+    // Представление числа в дополнительном коде длиной len разрядов (len должно вмещать знаковый бит)
+    fn to_twos_complement(&self, len: usize) -> Vec<u32> {
+        let mut limbs = self.digits.clone();
+        limbs.resize(len, 0);
+        if let Sign::Negative = self.sign {
+            negate_digits_twos(&mut limbs); // -x = !x + 1
+        }
+        limbs
+    }
+
+    // Восстановление числа из дополнительного кода: старший бит последнего разряда — знак
+    fn from_twos_complement(mut limbs: Vec<u32>) -> MyBigInt {
+        let mut result = MyBigInt::default();
+        if limbs.last().is_some_and(|&d| d >> (BITS - 1) == 1) {
+            negate_digits_twos(&mut limbs);
+            result.sign = Sign::Negative;
+        }
+        result.digits = limbs;
+        result.trim();
+        result
+    }
+
+    // Общая схема побитовых операций: оба числа переводятся в дополнительный код одинаковой длины
+    // (на разряд длиннее большего из них, чтобы знаковое расширение было видно), затем op применяется поразрядно
+    fn bitwise(&self, other: &MyBigInt, op: impl Fn(u32, u32) -> u32) -> MyBigInt {
+        let mut result = self.clone();
+        result.bitwise_assign(other, op);
+        result
+    }
+
+    // Та же схема на месте: дополнительный код self строится в его же буфере,
+    // а разряды other в дополнительном коде вычисляются на лету, без промежуточного вектора
+    fn bitwise_assign(&mut self, other: &MyBigInt, op: impl Fn(u32, u32) -> u32) {
+        let len = self.digits.len().max(other.digits.len()) + 1;
+        self.digits.resize(len, 0);
+        if self.is_negative() {
+            negate_digits_twos(&mut self.digits);
+        }
+        let other_negative = other.is_negative();
+        let mut carry = 1u64;
+        for (i, d) in self.digits.iter_mut().enumerate() {
+            let mut y = other.digits.get(i).copied().unwrap_or(0);
+            if other_negative {
+                let t = (!y) as u64 + carry;
+                y = t as u32;
+                carry = t >> BITS;
+            }
+            *d = op(*d, y);
+        }
+        self.sign = Sign::Positive;
+        if self.digits[len - 1] >> (BITS - 1) == 1 {
+            negate_digits_twos(&mut self.digits);
+            self.sign = Sign::Negative;
+        }
+        self.trim();
+    }
+
+    // побитовое НЕ в дополнительном коде бесконечной точности: !x = -(x + 1)
+    pub fn not(&self) -> MyBigInt {
+        let mut result = self.add(&MyBigInt::from_limb(1));
+        result.sign = result.sign.swap();
+        result.trim();
+        result
+    }
+
+    // побитовая инверсия на месте
+    pub fn inv(&mut self) {
+        let result = MyBigInt::not(self);
+        self.sign = result.sign;
+        self.digits = result.digits;
+    }
+
+    // побитовое исключительное ИЛИ
+    pub fn xor(&self, other: &MyBigInt) -> MyBigInt {
+        self.bitwise(other, |a, b| a ^ b)
+    }
+
+    // побитовое ИЛИ
+    pub fn or(&self, other: &MyBigInt) -> MyBigInt {
+        self.bitwise(other, |a, b| a | b)
+    }
+
+    // побитовое И
+    pub fn and(&self, other: &MyBigInt) -> MyBigInt {
+        self.bitwise(other, |a, b| a & b)
+    }
+
+    // сдвиг в право на n битов
+    pub fn shift_r(&self, n: usize) -> MyBigInt {
+        let mut result = self.clone();
+        result.shr_assign(n);
+        result
+    }
+
+    // сдвиг в лево на n битов
+    pub fn shift_l(&self, n: usize) -> MyBigInt {
+        let mut result = self.clone();
+        result.shl_assign(n);
+        result
+    }
+
+    // сдвиг в лево на n битов на месте
+    pub fn shl_assign(&mut self, n: usize) {
+        let limbs = n / BITS as usize; // Количество целых разрядов, на которое сдвигается число
+        let bits = (n % BITS as usize) as u32; // Оставшийся сдвиг внутри разряда
+
+        // Сдвигаем каждый разряд на bits, перенося вытесненные старшие биты в следующий разряд
+        if bits > 0 {
+            let mut carry = 0u32;
+            for d in self.digits.iter_mut() {
+                let x = *d;
+                *d = (x << bits) | carry;
+                carry = x >> (BITS - bits);
+            }
+            if carry != 0 {
+                self.digits.push(carry);
+            }
+        }
+
+        // Сдвиг на целые разряды: одна вставка нулей в начало вместо insert(0, ..) в цикле
+        if limbs > 0 && self.digits.iter().any(|&d| d != 0) {
+            self.digits.splice(0..0, std::iter::repeat_n(0, limbs));
+        }
+
+        self.trim();
+    }
+
+    // сдвиг в право на n битов на месте (арифметический: результат округляется к минус бесконечности)
+    pub fn shr_assign(&mut self, n: usize) {
+        let limbs = n / BITS as usize;
+        let bits = (n % BITS as usize) as u32;
+
+        // Для отрицательного числа, у которого отбрасываются ненулевые биты, модуль результата увеличивается на 1
+        let round_down = self.is_negative()
+            && (self.digits.iter().take(limbs).any(|&d| d != 0)
+                || (bits > 0 && self.digits.get(limbs).is_some_and(|&d| d & ((1 << bits) - 1) != 0)));
+
+        // Число сдвигается целиком за пределы разрядной сетки
+        if limbs >= self.digits.len() {
+            self.digits.clear();
+        } else {
+            self.digits.drain(..limbs); // Отбрасываем младшие разряды целиком
+
+            // Сдвигаем оставшиеся разряды, начиная со старшего, перенося младшие биты в предыдущий разряд
+            if bits > 0 {
+                let mut carry = 0u32;
+                for d in self.digits.iter_mut().rev() {
+                    let x = *d;
+                    *d = (x >> bits) | carry;
+                    carry = x << (BITS - bits);
+                }
+            }
+        }
+
+        self.trim();
+        if round_down {
+            self.digits = add_digits(&self.digits, &[1]);
+            self.sign = Sign::Negative;
+        }
+    }
+
+    // ADD 
+    pub fn add(&self, other: &MyBigInt) -> MyBigInt {
+        self.add_signed(other, other.sign.clone())
+    }
+
+    // SUB 
+    pub fn sub(&self, other: &MyBigInt) -> MyBigInt {
+        // Вычитание сводится к сложению с числом противоположного знака
+        self.add_signed(other, other.sign.clone().swap())
+    }
+
+    // MUL 
+    pub fn mul(&self, other: &MyBigInt) -> MyBigInt {
+        let mut result = MyBigInt {
+            digits: mul_digits(&self.digits, &other.digits),
+            // Произведение отрицательно, только если знаки множителей различны
+            sign: match (&self.sign, &other.sign) {
+                (Sign::Positive, Sign::Positive) | (Sign::Negative, Sign::Negative) => Sign::Positive,
+                _ => Sign::Negative,
+            },
+            ..Default::default()
+        };
+        result.trim();
+        result
+    }
+
+    // Сложение self с числом, модуль которого равен модулю other, а знак равен other_sign
+    fn add_signed(&self, other: &MyBigInt, other_sign: Sign) -> MyBigInt {
+        let mut result = MyBigInt::default();
+
+        match (&self.sign, &other_sign) {
+            // Знаки совпадают: складываем модули, знак сохраняется
+            (Sign::Positive, Sign::Positive) | (Sign::Negative, Sign::Negative) => {
+                result.digits = add_digits(&self.digits, &other.digits);
+                result.sign = self.sign.clone();
+            }
+            // Знаки разные: из большего модуля вычитаем меньший, знак берем у большего по модулю
+            _ => {
+                if cmp_digits(&self.digits, &other.digits) == Ordering::Less {
+                    result.digits = sub_digits(&other.digits, &self.digits);
+                    result.sign = other_sign;
+                } else {
+                    result.digits = sub_digits(&self.digits, &other.digits);
+                    result.sign = self.sign.clone();
+                }
+            }
+        }
+
+        result.trim(); // Убираем ведущие нули и нормализуем знак нуля
+        result
+    }
+
+    // Сложение на месте: self += число с модулем other и знаком other_sign (буфер self переиспользуется)
+    fn add_assign_signed(&mut self, other: &MyBigInt, other_sign: Sign) {
+        match (&self.sign, &other_sign) {
+            (Sign::Positive, Sign::Positive) | (Sign::Negative, Sign::Negative) => {
+                add_digits_assign(&mut self.digits, &other.digits);
+            }
+            _ => {
+                if cmp_digits(&self.digits, &other.digits) == Ordering::Less {
+                    self.digits = sub_digits(&other.digits, &self.digits);
+                    self.sign = other_sign;
+                } else {
+                    sub_digits_assign(&mut self.digits, &other.digits);
+                }
+            }
+        }
+        self.trim();
+    }
+
+    // Деление с остатком с усечением к нулю: self = q * other + r, знак r совпадает со знаком self
+    pub fn div_rem(&self, other: &MyBigInt) -> (MyBigInt, MyBigInt) {
+        self.checked_div_rem(other).expect("attempt to divide by zero")
+    }
+
+    // Вариант div_rem, который возвращает ошибку при делении на ноль
+    pub fn checked_div_rem(&self, other: &MyBigInt) -> Result<(MyBigInt, MyBigInt), DivisionByZero> {
+        if other.is_zero() {
+            return Err(DivisionByZero);
+        }
+
+        let (q, r) = div_rem_digits(&self.digits, &other.digits);
+        let mut quotient = MyBigInt {
+            digits: q,
+            sign: match (&self.sign, &other.sign) {
+                (Sign::Positive, Sign::Positive) | (Sign::Negative, Sign::Negative) => Sign::Positive,
+                _ => Sign::Negative,
+            },
+            ..Default::default()
+        };
+        quotient.trim();
+
+        let mut remainder = MyBigInt {
+            digits: r,
+            sign: self.sign.clone(),
+            ..Default::default()
+        };
+        remainder.trim();
+
+        Ok((quotient, remainder))
+    }
+
+    // DIV (частное с усечением к нулю)
+    pub fn div(&self, other: &MyBigInt) -> MyBigInt {
+        self.div_rem(other).0
+    }
+
+    // REM (остаток со знаком делимого)
+    pub fn rem(&self, other: &MyBigInt) -> MyBigInt {
+        self.div_rem(other).1
+    }
+
+    // Деление с округлением частного к минус бесконечности: знак остатка совпадает со знаком other
+    pub fn div_mod_floor(&self, other: &MyBigInt) -> (MyBigInt, MyBigInt) {
+        let (mut q, mut r) = self.div_rem(other);
+        if !r.is_zero() && r.is_negative() != other.is_negative() {
+            q = q.sub(&MyBigInt::from_limb(1));
+            r = r.add(other);
+        }
+        (q, r)
+    }
+
+    pub fn div_floor(&self, other: &MyBigInt) -> MyBigInt {
+        self.div_mod_floor(other).0
+    }
+
+    pub fn mod_floor(&self, other: &MyBigInt) -> MyBigInt {
+        self.div_mod_floor(other).1
+    }
+
+    // Евклидово деление: остаток всегда лежит в [0, |other|)
+    pub fn div_rem_euclid(&self, other: &MyBigInt) -> (MyBigInt, MyBigInt) {
+        let (mut q, mut r) = self.div_rem(other);
+        if r.is_negative() {
+            if other.is_negative() {
+                q = q.add(&MyBigInt::from_limb(1));
+                r = r.sub(other);
+            } else {
+                q = q.sub(&MyBigInt::from_limb(1));
+                r = r.add(other);
+            }
+        }
+        (q, r)
+    }
+
+    pub fn div_euclid(&self, other: &MyBigInt) -> MyBigInt {
+        self.div_rem_euclid(other).0
+    }
+
+    pub fn rem_euclid(&self, other: &MyBigInt) -> MyBigInt {
+        self.div_rem_euclid(other).1
+    }
+
+    // MOD (евклидов остаток от деления, всегда неотрицательный)
+    pub fn modulus(&self, other: &MyBigInt) -> MyBigInt {
+        self.rem_euclid(other)
+    }
+
+    // Проверка на ноль (учитывает и ненормализованные разряды вида [0, 0])
+    pub fn is_zero(&self) -> bool {
+        self.digits.iter().all(|&d| d == 0)
+    }
+
+    // Число строго меньше нуля
+    pub fn is_negative(&self) -> bool {
+        self.sign == Sign::Negative && !self.is_zero()
+    }
+
+    // Модуль числа
+    pub fn abs(&self) -> MyBigInt {
+        let mut result = self.clone();
+        result.sign = Sign::Positive;
+        result
+    }
+
+    // Знак числа: -1, 0 или 1
+    pub fn signum(&self) -> MyBigInt {
+        if self.is_zero() {
+            MyBigInt::from_limb(0)
+        } else {
+            MyBigInt {
+                digits: vec![1],
+                sign: self.sign.clone(),
+                ..Default::default()
+            }
+        }
+    }
+
+    // Сравнение модулей чисел без учета знака
+    pub fn cmp_abs(&self, other: &MyBigInt) -> Ordering {
+        cmp_digits(&self.digits, &other.digits)
+    }
+
+    // Число из одного разряда
+    fn from_limb(value: u32) -> MyBigInt {
+        MyBigInt {
+            digits: vec![value],
+            ..Default::default()
+        }
+    }
+
+    // Число по модулю magnitude и знаку sign
+    fn from_u128(magnitude: u128, sign: Sign) -> MyBigInt {
+        let mut result = MyBigInt {
+            digits: (0..4).map(|i| (magnitude >> (i * BITS)) as u32).collect(),
+            sign,
+            ..Default::default()
+        };
+        result.trim();
+        result
+    }
+
+    // Модуль числа, если он помещается в u128
+    fn magnitude_u128(&self) -> Option<u128> {
+        let len = self.digits.len() - self.digits.iter().rev().take_while(|&&d| d == 0).count();
+        if len > 4 {
+            return None;
+        }
+        Some(self.digits[..len].iter().rev().fold(0u128, |acc, &d| (acc << BITS) | d as u128))
+    }
+
+    // Младшие 128 бит числа в дополнительном коде (как при приведении `as` между примитивами)
+    pub fn to_u128_wrapping(&self) -> u128 {
+        let low = self.digits.iter().take(4).rev().fold(0u128, |acc, &d| (acc << BITS) | d as u128);
+        if self.is_negative() {
+            low.wrapping_neg()
+        } else {
+            low
+        }
+    }
+
+    pub fn to_i128_wrapping(&self) -> i128 {
+        self.to_u128_wrapping() as i128
+    }
+
+    pub fn to_u64_wrapping(&self) -> u64 {
+        self.to_u128_wrapping() as u64
+    }
+
+    pub fn to_i64_wrapping(&self) -> i64 {
+        self.to_u128_wrapping() as i64
+    }
+
+    pub fn to_u32_wrapping(&self) -> u32 {
+        self.to_u128_wrapping() as u32
+    }
+
+    pub fn to_i32_wrapping(&self) -> i32 {
+        self.to_u128_wrapping() as i32
+    }
+}
+
+// Перевод между системами счисления.
+// Для radix, не являющихся степенью двойки, используется таблица степеней powers[k] = radix^(c·2^k),
+// где c — количество цифр, помещающихся в один разряд; число делится пополам по такой степени,
+// поэтому сложность определяется сложностью умножения (Карацуба), а не квадратична.
+impl MyBigInt {
+    // Количество значащих бит модуля числа
+    pub fn bits(&self) -> usize {
+        let len = self.digits.len() - self.digits.iter().rev().take_while(|&&d| d == 0).count();
+        if len == 0 {
+            0
+        } else {
+            (len - 1) * BITS as usize + (BITS - self.digits[len - 1].leading_zeros()) as usize
+        }
+    }
+
+    // Строковое представление в системе счисления radix (2..=36), цифры больше 9 — строчные латинские буквы
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "to_str_radix: radix must lie in the range [2, 36]");
+
+        if self.is_zero() {
+            return String::from("0");
+        }
+        let mut out = String::new();
+        if self.is_negative() {
+            out.push('-');
+        }
+
+        if radix.is_power_of_two() {
+            // Для степеней двойки каждая цифра — это просто группа бит
+            let bits_per_digit = radix.trailing_zeros() as usize;
+            let count = self.bits().div_ceil(bits_per_digit);
+            for i in (0..count).rev() {
+                let mut value = 0u32;
+                for b in (0..bits_per_digit).rev() {
+                    let pos = i * bits_per_digit + b;
+                    let bit = self.digits.get(pos / BITS as usize).map_or(0, |&d| (d >> (pos % BITS as usize)) & 1);
+                    value = (value << 1) | bit;
+                }
+                out.push(char::from_digit(value, radix).unwrap());
+            }
+        } else {
+            let magnitude = self.abs();
+            let (chunk, _) = radix_chunk(radix);
+
+            // Строим степени, пока квадрат последней не превысит число
+            let mut powers = vec![MyBigInt::from(chunk)];
+            loop {
+                let last = powers.last().unwrap();
+                let next = MyBigInt::mul(last, last);
+                if next > magnitude {
+                    break;
+                }
+                powers.push(next);
+            }
+            let reciprocals: Vec<MyBigInt> = powers.iter().map(MyBigInt::reciprocal).collect();
+
+            magnitude.write_radix(radix, &powers, &reciprocals, powers.len() - 1, 0, &mut out);
+        }
+        out
+    }
+
+    // Запись неотрицательного числа self < powers[level]^2 в out, дополненная нулями слева до width цифр
+    fn write_radix(
+        &self,
+        radix: u32,
+        powers: &[MyBigInt],
+        reciprocals: &[MyBigInt],
+        level: usize,
+        width: usize,
+        out: &mut String,
+    ) {
+        if level == 0 || self.digits.len() <= RADIX_DC_THRESHOLD {
+            // Небольшое число: последовательно делим на radix^c и выписываем группы по c цифр
+            let (chunk, chunk_digits) = radix_chunk(radix);
+            let mut rest = self.digits.clone();
+            let mut groups = Vec::new();
+            while rest.iter().any(|&d| d != 0) {
+                groups.push(div_rem_digits_small(&mut rest, chunk));
+            }
+
+            let mut text = String::new();
+            for (i, &group) in groups.iter().rev().enumerate() {
+                let mut digits = Vec::with_capacity(chunk_digits);
+                let mut g = group;
+                while g > 0 {
+                    digits.push(char::from_digit(g % radix, radix).unwrap());
+                    g /= radix;
+                }
+                if i > 0 {
+                    digits.resize(chunk_digits, '0'); // Внутренние группы дополняем нулями
+                }
+                text.extend(digits.iter().rev());
+            }
+
+            for _ in text.len()..width {
+                out.push('0');
+            }
+            out.push_str(&text);
+            return;
+        }
+
+        let power = &powers[level];
+        if *self < *power {
+            self.write_radix(radix, powers, reciprocals, level - 1, width, out);
+            return;
+        }
+
+        // self = high · powers[level] + low, low занимает ровно c·2^level цифр
+        let (high, low) = self.div_rem_reciprocal(power, &reciprocals[level]);
+        let low_width = radix_chunk(radix).1 << level;
+        high.write_radix(radix, powers, reciprocals, level - 1, width.saturating_sub(low_width), out);
+        low.write_radix(radix, powers, reciprocals, level - 1, low_width, out);
+    }
+
+    // Сборка числа из цифр values (от старших к младшим) в системе счисления radix;
+    // powers[k] = radix^(c·2^k) кэшируются между рекурсивными вызовами
+    fn from_radix_values(values: &[u32], radix: u32, powers: &mut Vec<MyBigInt>) -> MyBigInt {
+        let (chunk, chunk_digits) = radix_chunk(radix);
+
+        if values.len() <= RADIX_DC_THRESHOLD * chunk_digits {
+            // Схема Горнера по группам из c цифр: digits = digits * radix^len + group
+            let mut digits = vec![0u32];
+            for group in values.chunks(chunk_digits) {
+                let multiplier = if group.len() == chunk_digits { chunk } else { radix.pow(group.len() as u32) };
+                let mut carry = group.iter().fold(0u64, |acc, &v| acc * radix as u64 + v as u64);
+                for d in digits.iter_mut() {
+                    let t = *d as u64 * multiplier as u64 + carry;
+                    *d = t as u32;
+                    carry = t >> BITS;
+                }
+                if carry > 0 {
+                    digits.push(carry as u32);
+                }
+            }
+            let mut result = MyBigInt {
+                digits,
+                ..Default::default()
+            };
+            result.trim();
+            return result;
+        }
+
+        // Младшая часть — ровно c·2^k цифр, где k максимально при c·2^k < len
+        let mut k = 0;
+        while chunk_digits << (k + 1) < values.len() {
+            k += 1;
+        }
+        if powers.is_empty() {
+            powers.push(MyBigInt::from(chunk));
+        }
+        while powers.len() <= k {
+            let last = powers.last().unwrap();
+            let next = MyBigInt::mul(last, last);
+            powers.push(next);
+        }
+
+        let split = values.len() - (chunk_digits << k);
+        let high = MyBigInt::from_radix_values(&values[..split], radix, powers);
+        let low = MyBigInt::from_radix_values(&values[split..], radix, powers);
+        MyBigInt::mul(&high, &powers[k]) + low
+    }
+
+    // Обратное число floor(2^(2n) / d), где n — количество бит d > 0.
+    // Для больших d используется итерация Ньютона по старшей половине бит с последующей коррекцией.
+    fn reciprocal(d: &MyBigInt) -> MyBigInt {
+        let n = d.bits();
+        let one = MyBigInt::from(1u32);
+        if d.digits.len() <= NEWTON_THRESHOLD {
+            return MyBigInt::div(&(one << (2 * n)), d);
+        }
+
+        // Приближение по старшим h битам: x0 = floor(2^(2h) / dh) · 2^(n-h)
+        let h = n / 2 + 2;
+        let x0 = MyBigInt::reciprocal(&d.shift_r(n - h)) << (n - h);
+
+        // Шаг Ньютона: x1 = 2·x0 - d·x0² / 2^(2n)
+        let mut x = x0.shift_l(1) - (MyBigInt::mul(d, &MyBigInt::mul(&x0, &x0)) >> (2 * n));
+
+        // Коррекция до точного значения: остаток 2^(2n) - d·x должен лежать в [0, d)
+        let mut r = (one << (2 * n)) - MyBigInt::mul(d, &x);
+        while r.is_negative() {
+            x -= 1u32;
+            r += d;
+        }
+        while r >= *d {
+            x += 1u32;
+            r -= d;
+        }
+        x
+    }
+
+    // Деление неотрицательного self < 2^(2n) на d с заранее вычисленным reciprocal = floor(2^(2n) / d)
+    fn div_rem_reciprocal(&self, d: &MyBigInt, reciprocal: &MyBigInt) -> (MyBigInt, MyBigInt) {
+        let n = d.bits();
+        let mut q = MyBigInt::mul(self, reciprocal) >> (2 * n); // Оценка частного снизу, ошибка не больше 2
+        let mut r = self - MyBigInt::mul(&q, d);
+        while r >= *d {
+            q += 1u32;
+            r -= d;
+        }
+        (q, r)
+    }
+}
+
+// Символы Лежандра, Якоби и Кронекера
+impl MyBigInt {
+    // Символ Якоби (self / n) для нечетного положительного n: 1, -1 или 0 (если gcd(self, n) != 1).
+    // Бинарный алгоритм: множители 2 выносятся по правилу (2 / n) = (-1)^((n² - 1) / 8),
+    // а затем аргументы меняются местами по квадратичному закону взаимности — только сдвиги и остатки, без возведения в степень.
+    pub fn jacobi(&self, n: &MyBigInt) -> i32 {
+        assert!(!n.is_negative() && n.test_bit(0), "jacobi: n must be odd and positive");
+        let mut a = self.rem_euclid(n);
+        let mut n = n.clone();
+        let mut result = 1;
+        while !a.is_zero() {
+            // (2 / n) = -1 при n ≡ 3, 5 (mod 8)
+            let z = a.trailing_zeros().unwrap();
+            a.shr_assign(z);
+            let n_mod_8 = n.digits[0] & 7;
+            if z % 2 == 1 && (n_mod_8 == 3 || n_mod_8 == 5) {
+                result = -result;
+            }
+            // Квадратичный закон взаимности для нечетных a и n
+            if a.digits[0] & 3 == 3 && n_mod_8 & 3 == 3 {
+                result = -result;
+            }
+            std::mem::swap(&mut a, &mut n);
+            a = &a % &n;
+        }
+        if n == MyBigInt::from(1u32) {
+            result
+        } else {
+            0
+        }
+    }
+
+    // Символ Лежандра (self / p) для нечетного простого p: 1 для квадратичного вычета, -1 для невычета,
+    // 0 если p делит self. Простота p не проверяется; для простого p совпадает с символом Якоби.
+    pub fn legendre(&self, p: &MyBigInt) -> i32 {
+        self.jacobi(p)
+    }
+
+    // Символ Кронекера (self / n) для любого целого n — обобщение символа Якоби на четные и отрицательные n:
+    // (a / 0) = 1 при |a| = 1 и 0 иначе, (a / -1) = -1 при a < 0, (a / 2) = 0 для четного a,
+    // 1 при a ≡ ±1 (mod 8) и -1 при a ≡ ±3 (mod 8).
+    pub fn kronecker(&self, n: &MyBigInt) -> i32 {
+        if n.is_zero() {
+            return if self.cmp_abs(&MyBigInt::from(1u32)) == Ordering::Equal { 1 } else { 0 };
+        }
+        let mut result = if n.is_negative() && self.is_negative() { -1 } else { 1 };
+
+        let v = n.trailing_zeros().unwrap();
+        if v > 0 {
+            if !self.test_bit(0) {
+                return 0;
+            }
+            let a_mod_8 = self.rem_euclid(&MyBigInt::from(8u32)).digits[0];
+            if v % 2 == 1 && (a_mod_8 == 3 || a_mod_8 == 5) {
+                result = -result;
+            }
+        }
+        result * self.jacobi(&(n.abs() >> v))
+    }
+}
+
+// Модульное возведение в степень
+impl MyBigInt {
+    // self^exp mod modulus, результат лежит в [0, modulus).
+    // Для отрицательного exp основание заменяется обратным по модулю; modulus должен быть положительным.
+    pub fn modpow(&self, exp: &MyBigInt, modulus: &MyBigInt) -> MyBigInt {
+        assert!(!modulus.is_zero() && !modulus.is_negative(), "modpow: modulus must be positive");
+
+        let mut base = self.rem_euclid(modulus);
+        if exp.is_negative() {
+            base = base.mod_inverse(modulus).expect("modpow: base is not invertible modulo modulus");
+        }
+        let exp = exp.abs();
+
+        if let Some(ctx) = MontgomeryCtx::new(modulus) {
+            ctx.from_montgomery(&ctx.pow(&ctx.to_montgomery(&base), &exp))
+        } else {
+            // Четный модуль (или единица): умножение с редукцией Барретта
+            let ctx = BarrettCtx::new(modulus).unwrap();
+            let one = ctx.reduce(&MyBigInt::from(1u32));
+            sliding_window_pow(&base, one, &exp, |acc, rhs| {
+                *acc = ctx.mul(acc, rhs.unwrap_or(acc));
+            })
+        }
+    }
+
+    // self·other mod modulus, результат лежит в [0, modulus).
+    // Для разового умножения хватает одного деления; при многих умножениях по одному модулю
+    // выгоднее один раз построить BarrettCtx и пользоваться ctx.mul
+    pub fn mod_mul(&self, other: &MyBigInt, modulus: &MyBigInt) -> MyBigInt {
+        assert!(!modulus.is_zero() && !modulus.is_negative(), "mod_mul: modulus must be positive");
+        MyBigInt::mul(self, other).rem_euclid(modulus)
+    }
+
+    // Значение бита с номером i модуля числа
+    fn test_bit(&self, i: usize) -> bool {
+        self.digits.get(i / BITS as usize).is_some_and(|&d| (d >> (i % BITS as usize)) & 1 == 1)
+    }
+}
+
+// Наибольший общий делитель и наименьшее общее кратное
+impl MyBigInt {
+    // Количество младших нулевых бит модуля числа, None для нуля
+    pub fn trailing_zeros(&self) -> Option<usize> {
+        let i = self.digits.iter().position(|&d| d != 0)?;
+        Some(i * BITS as usize + self.digits[i].trailing_zeros() as usize)
+    }
+
+    // gcd(self, other) >= 0, gcd(0, 0) = 0.
+    // Бинарный алгоритм: общая степень двойки выносится сразу, далее из большего нечетного числа
+    // вычитается меньшее и из разности удаляются нулевые биты — только вычитания и сдвиги на месте, без деления.
+    pub fn gcd(&self, other: &MyBigInt) -> MyBigInt {
+        let mut a = self.abs();
+        let mut b = other.abs();
+        let (za, zb) = match (a.trailing_zeros(), b.trailing_zeros()) {
+            (None, _) => return b,
+            (_, None) => return a,
+            (Some(za), Some(zb)) => (za, zb),
+        };
+        a.shr_assign(za);
+        b.shr_assign(zb);
+        loop {
+            // a и b нечетны
+            if cmp_digits(&a.digits, &b.digits) == Ordering::Greater {
+                std::mem::swap(&mut a, &mut b);
+            }
+            sub_digits_assign(&mut b.digits, &a.digits);
+            b.trim();
+            match b.trailing_zeros() {
+                None => break,
+                Some(z) => b.shr_assign(z),
+            }
+        }
+        a.shl_assign(za.min(zb));
+        a
+    }
+
+    // lcm(self, other) >= 0, lcm(x, 0) = 0
+    pub fn lcm(&self, other: &MyBigInt) -> MyBigInt {
+        if self.is_zero() || other.is_zero() {
+            return MyBigInt::from(0u32);
+        }
+        let g = self.gcd(other);
+        MyBigInt::mul(&self.abs().div_rem(&g).0, &other.abs())
+    }
+
+    // Расширенный алгоритм Евклида: (g, x, y), где g = gcd(self, other) >= 0 и self·x + other·y = g.
+    // Коэффициенты минимальны по модулю: |x| <= |other| / (2g), |y| <= |self| / (2g) (кроме вырожденных случаев).
+    // Алгоритм Лемера: последовательность частных Евклида моделируется на старших 63 битах в машинной арифметике,
+    // а к длинным числам применяется только накопленная матрица 2×2 — одна длинная операция примерно на 30 шагов Евклида.
+    // Отслеживается только коэффициент при self; y восстанавливается в конце одним точным делением.
+    pub fn extended_gcd(&self, other: &MyBigInt) -> (MyBigInt, MyBigInt, MyBigInt) {
+        let (a0, b0) = (self.abs(), other.abs());
+        if b0.is_zero() {
+            let x = if a0.is_zero() { 0 } else if self.is_negative() { -1 } else { 1 };
+            return (a0, MyBigInt::from(x), MyBigInt::from(0u32));
+        }
+
+        // Инвариант: a ≡ s0·|self|, b ≡ s1·|self| (mod |other|), a >= b
+        let (mut a, mut b) = (a0.clone(), b0.clone());
+        let (mut s0, mut s1) = (MyBigInt::from(1u32), MyBigInt::from(0u32));
+        if a < b {
+            std::mem::swap(&mut a, &mut b);
+            std::mem::swap(&mut s0, &mut s1);
+        }
+
+        while !b.is_zero() {
+            let h = a.bits().saturating_sub(63);
+            let mut x = (&a >> h).to_u128_wrapping() as i128;
+            let mut y = (&b >> h).to_u128_wrapping() as i128;
+            let (mut ma, mut mb, mut mc, mut md) = (1i128, 0i128, 0i128, 1i128);
+
+            if h == 0 {
+                // Оба числа помещаются в машинное слово: доводим алгоритм до конца без длинной арифметики
+                while y != 0 {
+                    let q = x / y;
+                    (x, y) = (y, x - q * y);
+                    (ma, mc) = (mc, ma - q * mc);
+                    (mb, md) = (md, mb - q * md);
+                }
+                a = MyBigInt::from(x);
+                s0 = &s0 * MyBigInt::from(ma) + &s1 * MyBigInt::from(mb);
+                break;
+            }
+
+            // Шаги, частное которых одинаково для обеих границ интервала, совпадают с шагами точного алгоритма (Кнут, алгоритм L)
+            while y + mc != 0 && y + md != 0 {
+                let q = (x + ma) / (y + mc);
+                if q != (x + mb) / (y + md) {
+                    break;
+                }
+                (ma, mc) = (mc, ma - q * mc);
+                (mb, md) = (md, mb - q * md);
+                (x, y) = (y, x - q * y);
+            }
+
+            if mb == 0 {
+                // Старших бит не хватило ни на один шаг — полный шаг деления
+                let (q, r) = a.div_rem(&b);
+                let s = &s0 - &q * &s1;
+                (a, b) = (b, r);
+                (s0, s1) = (s1, s);
+            } else {
+                let (ma, mb, mc, md) = (MyBigInt::from(ma), MyBigInt::from(mb), MyBigInt::from(mc), MyBigInt::from(md));
+                (a, b) = (&a * &ma + &b * &mb, &a * &mc + &b * &md);
+                (s0, s1) = (&s0 * &ma + &s1 * &mb, &s0 * &mc + &s1 * &md);
+            }
+        }
+
+        // Коэффициент при other из a = s0·|self| + t·|other|
+        let mut t = (&a - &s0 * &a0) / &b0;
+        // Коэффициенты найдены для |self| и |other|, возвращаем знаки
+        if self.is_negative() {
+            s0 = -s0;
+        }
+        if other.is_negative() {
+            t = -t;
+        }
+        (a, s0, t)
+    }
+}
+
+// Обратный элемент по модулю
+impl MyBigInt {
+    // x из [0, modulus) такой, что self·x ≡ 1 (mod modulus); self может быть отрицательным или больше modulus.
+    // modulus должен быть положительным.
+    pub fn mod_inverse(&self, modulus: &MyBigInt) -> Result<MyBigInt, NotInvertible> {
+        assert!(!modulus.is_zero() && !modulus.is_negative(), "mod_inverse: modulus must be positive");
+        let (g, x, _) = self.rem_euclid(modulus).extended_gcd(modulus);
+        if g != MyBigInt::from(1u32) {
+            return Err(NotInvertible { gcd: g });
+        }
+        Ok(x.rem_euclid(modulus))
+    }
+
+    // Обращение всех элементов среза по модулю трюком Монтгомери: одно обращение произведения
+    // и 3(n - 1) умножений вместо n обращений. Если какой-то элемент необратим, возвращается ошибка
+    // для первого такого элемента.
+    pub fn batch_mod_inverse(values: &[MyBigInt], modulus: &MyBigInt) -> Result<Vec<MyBigInt>, NotInvertible> {
+        let ctx = BarrettCtx::new(modulus).expect("batch_mod_inverse: modulus must be positive");
+        if values.is_empty() {
+            return Ok(Vec::new());
+        }
+        let reduced: Vec<MyBigInt> = values.iter().map(|v| ctx.reduce(v)).collect();
+
+        // prefix[i] = values[0]·…·values[i] mod modulus
+        let mut prefix = Vec::with_capacity(reduced.len());
+        prefix.push(reduced[0].clone());
+        for v in &reduced[1..] {
+            let p = ctx.mul(prefix.last().unwrap(), v);
+            prefix.push(p);
+        }
+
+        let mut inv = match prefix.last().unwrap().mod_inverse(modulus) {
+            Ok(inv) => inv,
+            Err(err) => {
+                // Произведение необратимо — ищем виновный элемент
+                return Err(reduced.iter().find_map(|v| v.mod_inverse(modulus).err()).unwrap_or(err));
+            }
+        };
+
+        let mut result = vec![MyBigInt::default(); reduced.len()];
+        for i in (1..reduced.len()).rev() {
+            result[i] = ctx.mul(&inv, &prefix[i - 1]);
+            inv = ctx.mul(&inv, &reduced[i]);
+        }
+        result[0] = inv;
+        Ok(result)
+    }
+}
+
+// Проверка на простоту
+impl MyBigInt {
+    // Вероятностная проверка простоты: пробное деление на малые простые, затем тест Миллера–Рабина
+    // по основанию 2 и rounds случайным основаниям (вероятность ошибки для составного числа не больше 4^(-rounds)).
+    // Для n < 2^64 используется детерминированный набор оснований и ответ точен при любом rounds.
+    pub fn is_probably_prime(&self, rounds: usize) -> bool {
+        if let Some(result) = self.trial_division() {
+            return result;
+        }
+        let ctx = MontgomeryCtx::new(self).unwrap();
+        if self.bits() <= 64 {
+            return miller_rabin(&ctx, self, MILLER_RABIN_BASES_U64.iter().map(|&b| MyBigInt::from(b)));
+        }
+
+        // Случайные основания из [2, n - 2]
+        let range = self - 3u32;
+        let mut rng = SplitMix64::from_random_state();
+        let random_bases = (0..rounds).map(move |_| MyBigInt::random_below(&range, &mut rng) + 2u32);
+        miller_rabin(&ctx, self, std::iter::once(MyBigInt::from(2u32)).chain(random_bases))
+    }
+
+    // Тест Бэйли–PSW: сильный тест Миллера–Рабина по основанию 2 и сильный тест Люка с параметрами Селфриджа.
+    // Составных чисел, проходящих оба теста, не известно; для n < 2^64 доказано, что их нет.
+    pub fn is_prime_bpsw(&self) -> bool {
+        if let Some(result) = self.trial_division() {
+            return result;
+        }
+        let ctx = MontgomeryCtx::new(self).unwrap();
+        miller_rabin(&ctx, self, std::iter::once(MyBigInt::from(2u32))) && self.is_strong_lucas_probable_prime()
+    }
+
+    // Остаток от деления модуля числа на d != 0
+    fn rem_u32(&self, d: u32) -> u32 {
+        self.digits.iter().rev().fold(0u64, |r, &x| ((r << BITS) | x as u64) % d as u64) as u32
+    }
+
+    // Пробное деление на простые меньше SMALL_PRIME_LIMIT.
+    // Some(ответ), если простота определена (в том числе для n < SMALL_PRIME_LIMIT²), иначе None — n нечетно и больше
+    fn trial_division(&self) -> Option<bool> {
+        if self.is_negative() || self.bits() <= 1 {
+            return Some(false);
+        }
+        if self.bits() <= 32 && (self.digits[0] as usize) < SMALL_PRIME_LIMIT {
+            return Some(SMALL_PRIMES.binary_search(&self.digits[0]).is_ok());
+        }
+        if SMALL_PRIMES.iter().any(|&p| self.rem_u32(p) == 0) {
+            return Some(false);
+        }
+        if self.bits() <= 64 && self.to_u64_wrapping() < (SMALL_PRIME_LIMIT * SMALL_PRIME_LIMIT) as u64 {
+            return Some(true);
+        }
+        None
+    }
+
+    // Сильный тест Люка с P = 1, Q = (1 - D) / 4, где D — первое из 5, -7, 9, -11, ... с (D / n) = -1
+    // (метод A Селфриджа). n нечетно и не делится на малые простые.
+    fn is_strong_lucas_probable_prime(&self) -> bool {
+        let n = self;
+        // Для полного квадрата подходящего D не существует
+        if n.is_perfect_square() {
+            return false;
+        }
+        let mut d: i64 = 5;
+        loop {
+            match MyBigInt::from(d).jacobi(n) {
+                -1 => break,
+                0 if n.cmp_abs(&MyBigInt::from(d)) != Ordering::Equal => return false,
+                _ => d = if d > 0 { -(d + 2) } else { -d + 2 },
+            }
+        }
+
+        let ctx = BarrettCtx::new(n).unwrap();
+        let d_mod = MyBigInt::from(d).rem_euclid(n);
+        let q_mod = MyBigInt::from((1 - d) / 4).rem_euclid(n);
+        // Деление на 2 по нечетному модулю для x < 2n
+        let half = |x: MyBigInt| {
+            let x = if x.test_bit(0) { x + n } else { x };
+            let x = x >> 1;
+            if x >= *n { x - n } else { x }
+        };
+
+        // n + 1 = k·2^s, k нечетно; вычисляем U_k, V_k и Q^k, проходя биты k от старших к младшим
+        let n_plus_1 = n + 1u32;
+        let s = n_plus_1.trailing_zeros().unwrap();
+        let k = &n_plus_1 >> s;
+        let (mut u, mut v, mut qk) = (MyBigInt::from(1u32), MyBigInt::from(1u32), q_mod.clone());
+        for i in (0..k.bits() - 1).rev() {
+            // Удвоение индекса: U_2m = U_m·V_m, V_2m = V_m² - 2Q^m
+            u = ctx.mul(&u, &v);
+            v = ctx.reduce(&(ctx.sqr(&v) - &qk - &qk));
+            qk = ctx.sqr(&qk);
+            if k.test_bit(i) {
+                // Увеличение индекса на 1: U_m+1 = (U_m + V_m) / 2, V_m+1 = (D·U_m + V_m) / 2
+                let next_u = half(&u + &v);
+                v = half(ctx.mul(&d_mod, &u) + &v);
+                u = next_u;
+                qk = ctx.mul(&qk, &q_mod);
+            }
+        }
+
+        if u.is_zero() || v.is_zero() {
+            return true;
+        }
+        for _ in 1..s {
+            v = ctx.reduce(&(ctx.sqr(&v) - &qk - &qk));
+            if v.is_zero() {
+                return true;
+            }
+            qk = ctx.sqr(&qk);
+        }
+        false
+    }
+}
+
+// Случайные числа с равномерным распределением
+impl MyBigInt {
+    // Равномерно распределенное число из [0, 2^bits)
+    pub fn random_bits<R: RngCore + ?Sized>(bits: usize, rng: &mut R) -> MyBigInt {
+        let limbs = bits.div_ceil(BITS as usize);
+        if limbs == 0 {
+            return MyBigInt::from(0u32);
+        }
+        // Один вызов fill_bytes на все число: для OsRng это одно чтение вместо чтения на каждый разряд
+        let mut bytes = vec![0u8; limbs * 4];
+        rng.fill_bytes(&mut bytes);
+        let mut x = MyBigInt {
+            digits: bytes.chunks_exact(4).map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]])).collect(),
+            ..Default::default()
+        };
+        x.digits[limbs - 1] &= u32::MAX >> (limbs * BITS as usize - bits);
+        x.trim();
+        x
+    }
+
+    // Равномерно распределенное число из [0, bound), bound > 0.
+    // Выборка с отклонением: берутся bound.bits() случайных бит, пока результат не окажется меньше bound,
+    // поэтому смещения, как у взятия остатка, нет; в среднем нужно меньше двух попыток.
+    pub fn random_below<R: RngCore + ?Sized>(bound: &MyBigInt, rng: &mut R) -> MyBigInt {
+        assert!(!bound.is_zero() && !bound.is_negative(), "random_below: bound must be positive");
+        let bits = bound.bits();
+        loop {
+            let x = MyBigInt::random_bits(bits, rng);
+            if x < *bound {
+                return x;
+            }
+        }
+    }
+
+    // Равномерно распределенное число из [lo, hi), lo < hi
+    pub fn random_range<R: RngCore + ?Sized>(lo: &MyBigInt, hi: &MyBigInt, rng: &mut R) -> MyBigInt {
+        assert!(lo < hi, "random_range: empty range");
+        lo + MyBigInt::random_below(&(hi - lo), rng)
+    }
+}
+
+// Генерация простых чисел
+impl MyBigInt {
+    // Случайное простое ровно из bits бит с двумя установленными старшими битами
+    // (произведение двух таких простых имеет ровно 2·bits бит, как требуется для модуля RSA). bits >= 2.
+    pub fn gen_prime<R: RngCore + ?Sized>(bits: usize, rng: &mut R) -> MyBigInt {
+        assert!(bits >= 2, "gen_prime: bits must be at least 2");
+        loop {
+            let start = MyBigInt::random_odd_with_top_bits(bits, 2, rng);
+            if start.bits() <= 32 && (start.digits[0] as usize) < SMALL_PRIME_LIMIT {
+                // Малые числа проверяются по таблице; решето исключило бы сами малые простые
+                if start.is_probably_prime(0) {
+                    return start;
+                }
+                continue;
+            }
+            let sieve = SmallPrimeSieve::new(&start);
+            for delta in (0..PRIME_SEARCH_WINDOW).step_by(2) {
+                if !sieve.is_coprime(delta) {
+                    continue;
+                }
+                let candidate = &start + MyBigInt::from(delta);
+                if candidate.bits() != bits {
+                    break; // Вышли за bits бит; старшие два бита остаются установленными, пока этого не случилось
+                }
+                if candidate.is_generated_prime() {
+                    return candidate;
+                }
+            }
+        }
+    }
+
+    // Случайное безопасное простое p = 2q + 1 (q тоже простое) ровно из bits бит, для групп Диффи–Хеллмана. bits >= 3.
+    // Решето отсеивает кандидатов, у которых делится на малое простое хотя бы одно из q и p.
+    pub fn gen_safe_prime<R: RngCore + ?Sized>(bits: usize, rng: &mut R) -> MyBigInt {
+        assert!(bits >= 3, "gen_safe_prime: bits must be at least 3");
+        loop {
+            let q_start = MyBigInt::random_odd_with_top_bits(bits - 1, 1, rng);
+            if q_start.bits() <= 32 && (q_start.digits[0] as usize) < SMALL_PRIME_LIMIT {
+                let p = (&q_start << 1) + 1u32;
+                if q_start.is_probably_prime(0) && p.is_probably_prime(0) {
+                    return p;
+                }
+                continue;
+            }
+            let sieve = SmallPrimeSieve::new(&q_start);
+            for delta in (0..PRIME_SEARCH_WINDOW).step_by(2) {
+                if !sieve.is_safe_coprime(delta) {
+                    continue;
+                }
+                let q = &q_start + MyBigInt::from(delta);
+                if q.bits() != bits - 1 {
+                    break;
+                }
+                let p = (&q << 1) + 1u32;
+                // Сначала дешевый тест по основанию 2 для обоих чисел, полная проверка — только для пар, прошедших его
+                let (q_ctx, p_ctx) = (MontgomeryCtx::new(&q).unwrap(), MontgomeryCtx::new(&p).unwrap());
+                let two = || std::iter::once(MyBigInt::from(2u32));
+                if miller_rabin(&q_ctx, &q, two())
+                    && miller_rabin(&p_ctx, &p, two())
+                    && q.is_generated_prime()
+                    && p.is_generated_prime()
+                {
+                    return p;
+                }
+            }
+        }
+    }
+
+    // Наименьшее простое, строго большее self
+    pub fn next_prime(&self) -> MyBigInt {
+        if self.is_negative() || self.bits() <= 1 {
+            return MyBigInt::from(2u32);
+        }
+        if self.bits() <= 32 {
+            if let Some(&p) = SMALL_PRIMES.iter().find(|&&p| p > self.digits[0]) {
+                return MyBigInt::from(p);
+            }
+        }
+        // Первый нечетный кандидат, больший self (и большего всех малых простых)
+        let mut start = self + 1u32;
+        if !start.test_bit(0) {
+            start += 1u32;
+        }
+        loop {
+            let sieve = SmallPrimeSieve::new(&start);
+            for delta in (0..PRIME_SEARCH_WINDOW).step_by(2) {
+                if sieve.is_coprime(delta) {
+                    let candidate = &start + MyBigInt::from(delta);
+                    if candidate.is_prime_bpsw() {
+                        return candidate;
+                    }
+                }
+            }
+            start += MyBigInt::from(PRIME_SEARCH_WINDOW);
+        }
+    }
+
+    // Случайное нечетное число ровно из bits бит, у которого установлены top старших бит
+    fn random_odd_with_top_bits<R: RngCore + ?Sized>(bits: usize, top: usize, rng: &mut R) -> MyBigInt {
+        let mut x = MyBigInt::random_bits(bits, rng);
+        x.digits.resize(bits.div_ceil(BITS as usize), 0);
+        for i in bits - top..bits {
+            x.digits[i / BITS as usize] |= 1 << (i % BITS as usize);
+        }
+        x.digits[0] |= 1;
+        x
+    }
+
+    // Проверка кандидата при генерации: Бэйли–PSW и несколько раундов Миллера–Рабина со случайными основаниями
+    fn is_generated_prime(&self) -> bool {
+        self.is_prime_bpsw() && self.is_probably_prime(PRIME_GEN_ROUNDS)
+    }
+}
+
+// Целые корни и точные степени
+impl MyBigInt {
+    // floor(sqrt(self)) для неотрицательного числа
+    pub fn isqrt(&self) -> MyBigInt {
+        assert!(!self.is_negative(), "isqrt: negative number");
+        self.nth_root(2)
+    }
+
+    // Целая часть корня n-й степени, n >= 1; результат округляется к нулю.
+    // Для отрицательного числа n должно быть нечетным.
+    // Метод Ньютона x' = ((n - 1)·x + a / x^(n-1)) / n от начального приближения 2^ceil(bits / n), не меньшего корня:
+    // последовательность строго убывает, пока не достигнет ответа.
+    pub fn nth_root(&self, n: u32) -> MyBigInt {
+        assert!(n > 0, "nth_root: n must be positive");
+        assert!(!self.is_negative() || n % 2 == 1, "nth_root: even root of a negative number");
+        if self.is_negative() {
+            return -self.abs().nth_root(n);
+        }
+        if n == 1 || self.bits() <= 1 {
+            return self.clone();
+        }
+        // self < 2^bits <= 2^n, значит корень равен 1; без этого при огромном n цикл возводил бы x в степень n - 1
+        if n as usize >= self.bits() {
+            return MyBigInt::from(1u32);
+        }
+        let n_big = MyBigInt::from(n);
+        let mut x = MyBigInt::from(1u32) << self.bits().div_ceil(n as usize);
+        loop {
+            let y = (MyBigInt::mul(&x, &MyBigInt::from(n - 1)) + self / &x.pow_u32(n - 1)) / &n_big;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    // Является ли число квадратом целого
+    pub fn is_perfect_square(&self) -> bool {
+        if self.is_negative() {
+            return false;
+        }
+        // Квадраты по модулю 16 дают только 0, 1, 4, 9 — это отсеивает 3/4 чисел без извлечения корня
+        if !matches!(self.digits.first().copied().unwrap_or(0) & 15, 0 | 1 | 4 | 9) {
+            return false;
+        }
+        let root = self.isqrt();
+        &root * &root == *self
+    }
+
+    // Представление self = base^exp с наибольшим возможным exp >= 2, None если его нет.
+    // Для 0 и 1 возвращается показатель 2, для -1 — показатель 3; у отрицательных чисел показатель нечетный.
+    pub fn is_perfect_power(&self) -> Option<(MyBigInt, u32)> {
+        if self.bits() <= 1 {
+            return Some((self.clone(), if self.is_negative() { 3 } else { 2 }));
+        }
+        // Показатель раскладывается на простые множители: пока base — точная p-я степень, извлекаем корень
+        let mut base = self.clone();
+        let mut exp = 1;
+        let mut p = 2u32;
+        while p as usize <= base.bits() {
+            while p != 2 || !base.is_negative() {
+                let root = base.nth_root(p);
+                if root.pow_u32(p) != base {
+                    break;
+                }
+                base = root;
+                exp *= p;
+            }
+            p = u32::try_from(&MyBigInt::from(p).next_prime()).unwrap();
+        }
+        if exp > 1 {
+            Some((base, exp))
+        } else {
+            None
+        }
+    }
+
+    // self^e
+    fn pow_u32(&self, e: u32) -> MyBigInt {
+        sliding_window_pow(self, MyBigInt::from(1u32), &MyBigInt::from(e), |acc, rhs| {
+            *acc = MyBigInt::mul(acc, rhs.unwrap_or(acc));
+        })
+    }
+}
+
+// Квадратные корни по простому модулю и по степени простого
+impl MyBigInt {
+    // Оба квадратных корня из self по нечетному простому модулю p (или p = 2): (r, p - r), r <= p - r;
+    // None, если self — квадратичный невычет. Простота p не проверяется: для составного p результат не определен.
+    // Частные случаи: p ≡ 3 (mod 4) — одно возведение в степень, p ≡ 5 (mod 8) — метод Аткина;
+    // иначе Тонелли–Шенкс, а при большой степени двойки в p - 1 — Циполла.
+    pub fn sqrt_mod_prime(&self, p: &MyBigInt) -> Option<(MyBigInt, MyBigInt)> {
+        assert!(!p.is_negative() && p.bits() >= 2, "sqrt_mod_prime: p must be a prime");
+        let a = self.rem_euclid(p);
+        if a.is_zero() || (p.bits() == 2 && !p.test_bit(0)) {
+            return Some((a.clone(), a)); // Корень 0 или модуль 2: корень единственный
+        }
+        if a.legendre(p) != 1 {
+            return None;
+        }
+
+        let ctx = BarrettCtx::new(p).unwrap();
+        let root = match p.digits[0] & 7 {
+            3 | 7 => a.modpow(&((p + 1u32) >> 2), p),
+            5 => {
+                // Аткин: v = (2a)^((p - 5) / 8), i = 2a·v² (квадратный корень из -1), r = a·v·(i - 1)
+                let two_a = ctx.reduce(&(&a << 1));
+                let v = two_a.modpow(&(p >> 3), p);
+                let i = ctx.mul(&two_a, &ctx.sqr(&v));
+                ctx.mul(&ctx.mul(&a, &v), &ctx.reduce(&(i - 1u32)))
+            }
+            _ => {
+                let s = (p - 1u32).trailing_zeros().unwrap();
+                // Тонелли–Шенкс делает до s²/2 лишних умножений, Циполла — около 2·bits
+                if s * s > 4 * p.bits() {
+                    a.sqrt_mod_cipolla(p, &ctx)
+                } else {
+                    a.sqrt_mod_tonelli_shanks(p, &ctx)
+                }
+            }
+        };
+
+        if ctx.sqr(&root) != a {
+            return None;
+        }
+        let other = p - &root;
+        Some(if root <= other { (root, other) } else { (other, root) })
+    }
+
+    // Оба квадратных корня из self по модулю p^k для нечетного простого p и self, взаимно простого с p:
+    // корень по модулю p поднимается леммой Гензеля r' = r - (r² - a)·(2r)^(-1), удваивая точность на каждом шаге.
+    // None, если self — невычет или делится на p (тогда корней либо нет, либо больше двух).
+    pub fn sqrt_mod_prime_power(&self, p: &MyBigInt, k: u32) -> Option<(MyBigInt, MyBigInt)> {
+        assert!(k >= 1, "sqrt_mod_prime_power: k must be positive");
+        assert!(!p.is_negative() && p.test_bit(0) && p.bits() >= 2, "sqrt_mod_prime_power: p must be an odd prime");
+        let (mut root, _) = self.sqrt_mod_prime(p)?;
+        if root.is_zero() {
+            return None;
+        }
+
+        let mut precision = 1;
+        while precision < k {
+            precision = (2 * precision).min(k);
+            let modulus = p.pow_u32(precision);
+            let residual = (&root * &root - self).rem_euclid(&modulus);
+            let inverse = (&root << 1).mod_inverse(&modulus).ok()?;
+            root = (&root - residual.mod_mul(&inverse, &modulus)).rem_euclid(&modulus);
+        }
+
+        let modulus = p.pow_u32(k);
+        let other = &modulus - &root;
+        Some(if root <= other { (root, other) } else { (other, root) })
+    }
+
+    // Тонелли–Шенкс для квадратичного вычета a по простому p, p - 1 = q·2^s
+    fn sqrt_mod_tonelli_shanks(&self, p: &MyBigInt, ctx: &BarrettCtx) -> MyBigInt {
+        let one = MyBigInt::from(1u32);
+        let p_minus_1 = p - 1u32;
+        let s = p_minus_1.trailing_zeros().unwrap();
+        let q = &p_minus_1 >> s;
+
+        let mut z = MyBigInt::from(2u32);
+        while z.legendre(p) != -1 {
+            z += 1u32;
+        }
+
+        // Инвариант: x² = a·t, c — первообразный корень степени 2^m из единицы
+        let mut m = s;
+        let mut c = z.modpow(&q, p);
+        let mut x = self.modpow(&((&q + 1u32) >> 1), p);
+        let mut t = self.modpow(&q, p);
+        while t != one {
+            // Наименьшее i < m с t^(2^i) = 1
+            let mut i = 0;
+            let mut t2 = t.clone();
+            while t2 != one {
+                t2 = ctx.sqr(&t2);
+                i += 1;
+            }
+            let mut b = c;
+            for _ in 0..m - i - 1 {
+                b = ctx.sqr(&b);
+            }
+            x = ctx.mul(&x, &b);
+            c = ctx.sqr(&b);
+            t = ctx.mul(&t, &c);
+            m = i;
+        }
+        x
+    }
+
+    // Циполла для квадратичного вычета a по простому p: при w = t² - a, являющемся невычетом,
+    // (t + √w)^((p + 1) / 2) в поле F_p(√w) равно корню из a
+    fn sqrt_mod_cipolla(&self, p: &MyBigInt, ctx: &BarrettCtx) -> MyBigInt {
+        let mut t = MyBigInt::from(1u32);
+        let w = loop {
+            let w = ctx.reduce(&(&t * &t - self));
+            if w.legendre(p) == -1 {
+                break w;
+            }
+            t += 1u32;
+        };
+
+        // Элементы F_p(√w) — пары (x, y) = x + y·√w
+        let base = (t, MyBigInt::from(1u32));
+        let one = (MyBigInt::from(1u32), MyBigInt::from(0u32));
+        let (x, _) = sliding_window_pow(&base, one, &((p + 1u32) >> 1), |acc, rhs| {
+            let (x2, y2) = rhs.unwrap_or(acc).clone();
+            let (x1, y1) = &*acc;
+            *acc = (
+                ctx.reduce(&(&ctx.mul(x1, &x2) + &ctx.mul(&ctx.mul(y1, &y2), &w))),
+                ctx.reduce(&(&ctx.mul(x1, &y2) + &ctx.mul(&x2, y1))),
+            );
+        });
+        x
+    }
+}
+
+// Китайская теорема об остатках
+impl MyBigInt {
+    // Решение системы x ≡ residues[i] (mod moduli[i]): (x, M), где M = lcm(moduli), x из [0, M).
+    // Модули должны быть положительными, но не обязаны быть взаимно простыми; если система несовместна, возвращается None.
+    // Сравнения объединяются попарно: x ≡ r1 (mod m1), x ≡ r2 (mod m2) разрешимо, только если g = gcd(m1, m2) делит r2 - r1.
+    pub fn crt(residues: &[MyBigInt], moduli: &[MyBigInt]) -> Option<(MyBigInt, MyBigInt)> {
+        assert_eq!(residues.len(), moduli.len(), "crt: residues and moduli must have the same length");
+        let mut x = MyBigInt::from(0u32);
+        let mut m = MyBigInt::from(1u32);
+        for (r, mi) in residues.iter().zip(moduli) {
+            assert!(!mi.is_zero() && !mi.is_negative(), "crt: moduli must be positive");
+            let (g, p, _) = m.extended_gcd(mi);
+            let (diff, rem) = (r - &x).div_rem(&g);
+            if !rem.is_zero() {
+                return None;
+            }
+            // x + m·t, где t ≡ diff·p (mod mi / g) и p = (m / g)^(-1) mod (mi / g)
+            let step = mi / &g;
+            let t = MyBigInt::mul(&diff, &p).rem_euclid(&step);
+            x += &m * &t;
+            m *= &step;
+        }
+        Some((x, m))
+    }
+}
+
+// Контекст арифметики Монтгомери для фиксированного нечетного модуля N из n разрядов.
+// Элементы хранятся в форме Монтгомери x·R mod N (R = 2^(32·n)) как срезы ровно из n разрядов u32;
+// mul, sqr работают с буферами вызывающего кода и не выделяют память.
+pub struct MontgomeryCtx {
+    modulus: Vec<u32>, // Разряды N без старших нулей
+    m_inv: u32,        // -N^(-1) mod 2^32
+    r1: Vec<u32>,      // R mod N — единица в форме Монтгомери
+    r2: Vec<u32>,      // R² mod N — для перевода в форму Монтгомери
+}
+
+impl MontgomeryCtx {
+    // Контекст для модуля modulus; None, если модуль четный или не больше единицы
+    pub fn new(modulus: &MyBigInt) -> Option<MontgomeryCtx> {
+        if modulus.is_negative() || !modulus.test_bit(0) || *modulus == MyBigInt::from(1u32) {
+            return None;
+        }
+        let n = modulus.digits.len() - modulus.digits.iter().rev().take_while(|&&d| d == 0).count();
+        let one = MyBigInt::from(1u32);
+        let limbs = |x: MyBigInt| {
+            let mut digits = x.digits;
+            digits.resize(n, 0);
+            digits
+        };
+
+        Some(MontgomeryCtx {
+            modulus: modulus.digits[..n].to_vec(),
+            m_inv: montgomery_inverse(modulus.digits[0]),
+            r1: limbs((&one << (BITS as usize * n)).rem(modulus)),
+            r2: limbs((&one << (2 * BITS as usize * n)).rem(modulus)),
+        })
+    }
+
+    // Количество разрядов n в элементах контекста
+    pub fn limbs(&self) -> usize {
+        self.modulus.len()
+    }
+
+    // Размер рабочего буфера для mul и sqr
+    pub fn scratch_len(&self) -> usize {
+        2 * self.modulus.len() + 1
+    }
+
+    pub fn modulus(&self) -> MyBigInt {
+        MyBigInt {
+            digits: self.modulus.clone(),
+            ..Default::default()
+        }
+    }
+
+    // Единица в форме Монтгомери (R mod N)
+    pub fn one(&self) -> &[u32] {
+        &self.r1
+    }
+
+    // Перевод x mod N в форму Монтгомери
+    pub fn to_montgomery(&self, x: &MyBigInt) -> Vec<u32> {
+        let mut limbs = x.rem_euclid(&self.modulus()).digits;
+        limbs.resize(self.limbs(), 0);
+        let mut out = vec![0u32; self.limbs()];
+        let mut scratch = vec![0u32; self.scratch_len()];
+        self.mul(&mut out, &limbs, &self.r2, &mut scratch);
+        out
+    }
+
+    // Обратный перевод из формы Монтгомери в обычное число из [0, N)
+    pub fn from_montgomery(&self, x: &[u32]) -> MyBigInt {
+        let mut unit = vec![0u32; self.limbs()];
+        unit[0] = 1;
+        let mut out = vec![0u32; self.limbs()];
+        let mut scratch = vec![0u32; self.scratch_len()];
+        self.mul(&mut out, x, &unit, &mut scratch);
+        let mut result = MyBigInt {
+            digits: out,
+            ..Default::default()
+        };
+        result.trim();
+        result
+    }
+
+    // out = a·b·R^(-1) mod N; scratch — буфер длиной не меньше scratch_len()
+    pub fn mul(&self, out: &mut [u32], a: &[u32], b: &[u32], scratch: &mut [u32]) {
+        let n = self.limbs();
+        montgomery_mul(out, a, b, &self.modulus, self.m_inv, &mut scratch[..n + 2]);
+    }
+
+    // out = a²·R^(-1) mod N; scratch — буфер длиной не меньше scratch_len()
+    pub fn sqr(&self, out: &mut [u32], a: &[u32], scratch: &mut [u32]) {
+        let n = self.limbs();
+        montgomery_sqr(out, a, &self.modulus, self.m_inv, &mut scratch[..2 * n + 1]);
+    }
+
+    // base^exp в форме Монтгомери (скользящее окно); память выделяется только под таблицу окна
+    pub fn pow(&self, base: &[u32], exp: &MyBigInt) -> Vec<u32> {
+        let mut tmp = vec![0u32; self.limbs()];
+        let mut scratch = vec![0u32; self.scratch_len()];
+        sliding_window_pow(&base.to_vec(), self.r1.clone(), exp, |acc, rhs| {
+            match rhs {
+                Some(b) => self.mul(&mut tmp, acc, b, &mut scratch),
+                None => self.sqr(&mut tmp, acc, &mut scratch),
+            }
+            std::mem::swap(acc, &mut tmp);
+        })
+    }
+}
+
+// Контекст редукции Барретта для фиксированного положительного модуля m из k разрядов (подходит и для четных m).
+// Предвычисляется μ = floor(b^(2k) / m), b = 2^32, после чего остаток от деления числа меньше b^(2k)
+// находится двумя умножениями и не более чем двумя вычитаниями вместо полного деления.
+pub struct BarrettCtx {
+    modulus: MyBigInt,
+    mu: MyBigInt,
+    k: usize,
+}
+
+impl BarrettCtx {
+    // Контекст для модуля modulus; None, если модуль не положителен
+    pub fn new(modulus: &MyBigInt) -> Option<BarrettCtx> {
+        if modulus.is_zero() || modulus.is_negative() {
+            return None;
+        }
+        let k = modulus.bits().div_ceil(BITS as usize);
+        let mu = (MyBigInt::from(1u32) << (2 * BITS as usize * k)).div(modulus);
+        let mut modulus = modulus.clone();
+        modulus.trim();
+        Some(BarrettCtx { modulus, mu, k })
+    }
+
+    pub fn modulus(&self) -> &MyBigInt {
+        &self.modulus
+    }
+
+    // x mod m в [0, m). Быстрый путь для 0 <= x < b^(2k), иначе обычное деление
+    pub fn reduce(&self, x: &MyBigInt) -> MyBigInt {
+        if x.is_negative() || x.bits() > 2 * BITS as usize * self.k {
+            return x.rem_euclid(&self.modulus);
+        }
+
+        // q = floor(floor(x / b^(k-1)) · μ / b^(k+1)) не превосходит floor(x / m) и отличается от него не больше чем на 2
+        let q = MyBigInt::mul(&x.shift_r(BITS as usize * (self.k - 1)), &self.mu) >> (BITS as usize * (self.k + 1));
+        let mut r = x - MyBigInt::mul(&q, &self.modulus);
+        while r >= self.modulus {
+            r -= &self.modulus;
+        }
+        r
+    }
+
+    // a·b mod m для a, b из [0, m)
+    pub fn mul(&self, a: &MyBigInt, b: &MyBigInt) -> MyBigInt {
+        self.reduce(&MyBigInt::mul(a, b))
+    }
+
+    // a² mod m для a из [0, m)
+    pub fn sqr(&self, a: &MyBigInt) -> MyBigInt {
+        self.reduce(&MyBigInt::mul(a, a))
+    }
+}
+
+// Предвычисленный базис для китайской теоремы об остатках с попарно взаимно простыми модулями m_0, ..., m_(k-1).
+// Восстановление идет алгоритмом Гарнера: x = v_0 + v_1·m_0 + v_2·m_0·m_1 + ..., цифры v_i вычисляются по модулю m_i
+// с предвычисленными (m_0·…·m_(i-1))^(-1) mod m_i, так что на каждый набор остатков не нужно ни одного обращения.
+// Для RSA-CRT с модулями (p, q) это ровно формула m = m_p + p·(p^(-1)·(m_q - m_p) mod q).
+pub struct CrtBasis {
+    contexts: Vec<BarrettCtx>, // Контексты Барретта для m_i
+    prefixes: Vec<MyBigInt>, // m_0·…·m_(i-1)
+    inverses: Vec<MyBigInt>, // (m_0·…·m_(i-1))^(-1) mod m_i
+    modulus: MyBigInt,       // m_0·…·m_(k-1)
+}
+
+impl CrtBasis {
+    // Базис для модулей moduli; None, если какой-то модуль не положителен или модули не взаимно просты попарно
+    pub fn new(moduli: &[MyBigInt]) -> Option<CrtBasis> {
+        let mut prefixes = Vec::with_capacity(moduli.len());
+        let mut inverses = Vec::with_capacity(moduli.len());
+        let mut modulus = MyBigInt::from(1u32);
+        for m in moduli {
+            if m.is_zero() || m.is_negative() {
+                return None;
+            }
+            inverses.push(modulus.mod_inverse(m).ok()?);
+            prefixes.push(modulus.clone());
+            modulus *= m;
+        }
+        let contexts = moduli.iter().map(|m| BarrettCtx::new(m).unwrap()).collect();
+        Some(CrtBasis { contexts, prefixes, inverses, modulus })
+    }
+
+    // Произведение модулей
+    pub fn modulus(&self) -> &MyBigInt {
+        &self.modulus
+    }
+
+    // Единственное x из [0, modulus) с x ≡ residues[i] (mod m_i)
+    pub fn combine(&self, residues: &[MyBigInt]) -> MyBigInt {
+        assert_eq!(residues.len(), self.contexts.len(), "CrtBasis::combine: wrong number of residues");
+        let mut x = MyBigInt::from(0u32);
+        for (i, r) in residues.iter().enumerate() {
+            let ctx = &self.contexts[i];
+            let v = ctx.mul(&ctx.reduce(&(r - &x)), &self.inverses[i]);
+            x += &self.prefixes[i] * &v;
+        }
+        x
+    }
+}
+
+// Байтовые представления. Беззнаковые варианты кодируют модуль числа (как OS2IP/I2OSP из PKCS#1),
+// знаковые — дополнительный код минимальной длины.
+impl MyBigInt {
+    // Неотрицательное число из байтов, старший байт первым
+    pub fn from_bytes_be(bytes: &[u8]) -> MyBigInt {
+        let reversed: Vec<u8> = bytes.iter().rev().copied().collect();
+        MyBigInt::from_bytes_le(&reversed)
+    }
+
+    // Неотрицательное число из байтов, младший байт первым
+    pub fn from_bytes_le(bytes: &[u8]) -> MyBigInt {
+        let mut result = MyBigInt {
+            digits: bytes
+                .chunks(4)
+                .map(|chunk| chunk.iter().rev().fold(0u32, |acc, &b| (acc << 8) | b as u32))
+                .collect(),
+            ..Default::default()
+        };
+        result.trim();
+        result
+    }
+
+    // Модуль числа байтами, старший байт первым, без ведущих нулей (ноль кодируется как [0])
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes_le();
+        bytes.reverse();
+        bytes
+    }
+
+    // Модуль числа байтами, младший байт первым, без старших нулей (ноль кодируется как [0])
+    pub fn to_bytes_le(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self.digits.iter().flat_map(|d| d.to_le_bytes()).collect();
+        while bytes.len() > 1 && bytes.last() == Some(&0) {
+            bytes.pop();
+        }
+        if bytes.is_empty() {
+            bytes.push(0);
+        }
+        bytes
+    }
+
+    // Модуль числа ровно в len байтах, старший байт первым (I2OSP)
+    pub fn to_bytes_be_padded(&self, len: usize) -> Result<Vec<u8>, IntegerTooLargeError> {
+        let bytes = self.to_bytes_be();
+        let significant = if self.is_zero() { &bytes[..0] } else { &bytes[..] };
+        if significant.len() > len {
+            return Err(IntegerTooLargeError);
+        }
+        let mut result = vec![0u8; len - significant.len()];
+        result.extend_from_slice(significant);
+        Ok(result)
+    }
+
+    // Число из дополнительного кода, старший байт первым
+    pub fn from_signed_bytes_be(bytes: &[u8]) -> MyBigInt {
+        let reversed: Vec<u8> = bytes.iter().rev().copied().collect();
+        MyBigInt::from_signed_bytes_le(&reversed)
+    }
+
+    // Число из дополнительного кода, младший байт первым (старший бит последнего байта — знак)
+    pub fn from_signed_bytes_le(bytes: &[u8]) -> MyBigInt {
+        let fill = if bytes.last().is_some_and(|&b| b >> 7 == 1) { 0xFF } else { 0 };
+        let mut extended = bytes.to_vec();
+        extended.resize(bytes.len().div_ceil(4).max(1) * 4, fill); // Знаковое расширение до целого числа разрядов
+        let limbs = extended.chunks(4).map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]])).collect();
+        MyBigInt::from_twos_complement(limbs)
+    }
+
+    // Дополнительный код минимальной длины, старший байт первым
+    pub fn to_signed_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_signed_bytes_le();
+        bytes.reverse();
+        bytes
+    }
+
+    // Дополнительный код минимальной длины, младший байт первым
+    pub fn to_signed_bytes_le(&self) -> Vec<u8> {
+        let limbs = self.to_twos_complement(self.digits.len() + 1);
+        let mut bytes: Vec<u8> = limbs.iter().flat_map(|d| d.to_le_bytes()).collect();
+        // Убираем старшие байты, которые только повторяют знаковое расширение
+        while bytes.len() > 1 {
+            let last = bytes[bytes.len() - 1];
+            let next_sign = bytes[bytes.len() - 2] >> 7;
+            if (last == 0 && next_sign == 0) || (last == 0xFF && next_sign == 1) {
+                bytes.pop();
+            } else {
+                break;
+            }
+        }
+        bytes
+    }
+}
+
+impl fmt::Display for MyBigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = self.abs().to_str_radix(10);
+        f.pad_integral(!self.is_negative(), "", &text)
+    }
+}
+
+// Форматирование в системах счисления по основанию 2^k. Цифры строятся по digits,
+// а знак, ширина, '+', '#' и дополнение нулями обрабатываются так же, как у примитивов (pad_integral).
+impl fmt::LowerHex for MyBigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(!self.is_negative(), "0x", &self.abs().to_str_radix(16))
+    }
+}
+
+impl fmt::UpperHex for MyBigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(!self.is_negative(), "0x", &self.abs().to_str_radix(16).to_uppercase())
+    }
+}
+
+impl fmt::Binary for MyBigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(!self.is_negative(), "0b", &self.abs().to_str_radix(2))
+    }
+}
+
+impl fmt::Octal for MyBigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(!self.is_negative(), "0o", &self.abs().to_str_radix(8))
+    }
+}
+
+// Компактный отладочный вывод: значение в шестнадцатеричном виде, без внутреннего BigNumber
+impl fmt::Debug for MyBigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MyBigInt({:#x})", self)
+    }
+}
+
+// Равенство по значению: +0 и -0 равны, вспомогательное поле other не учитывается
+impl PartialEq for MyBigInt {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MyBigInt {}
+
+// Сравнение с учетом знака: любое отрицательное число меньше любого неотрицательного
+impl Ord for MyBigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (false, false) => self.cmp_abs(other),
+            (true, true) => other.cmp_abs(self),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for MyBigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Хэш согласован с Eq: учитываются только значимые разряды и знак ненулевого числа
+impl std::hash::Hash for MyBigInt {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let len = self.digits.len() - self.digits.iter().rev().take_while(|&&d| d == 0).count();
+        self.is_negative().hash(state);
+        self.digits[..len].hash(state);
+    }
+}
+
+// Преобразования из беззнаковых примитивов и обратно (с проверкой диапазона)
+macro_rules! impl_unsigned_conversions {
+    ($($t:ty),*) => {$(
+        impl From<$t> for MyBigInt {
+            fn from(value: $t) -> MyBigInt {
+                MyBigInt::from_u128(value as u128, Sign::Positive)
+            }
+        }
+
+        impl TryFrom<&MyBigInt> for $t {
+            type Error = TryFromBigIntError;
+
+            fn try_from(value: &MyBigInt) -> Result<$t, TryFromBigIntError> {
+                if value.is_negative() {
+                    return Err(TryFromBigIntError);
+                }
+                let magnitude = value.magnitude_u128().ok_or(TryFromBigIntError)?;
+                <$t>::try_from(magnitude).map_err(|_| TryFromBigIntError)
+            }
+        }
+    )*};
+}
+
+// Преобразования из знаковых примитивов и обратно (с проверкой диапазона)
+macro_rules! impl_signed_conversions {
+    ($($t:ty),*) => {$(
+        impl From<$t> for MyBigInt {
+            fn from(value: $t) -> MyBigInt {
+                let sign = if value < 0 { Sign::Negative } else { Sign::Positive };
+                MyBigInt::from_u128(value.unsigned_abs() as u128, sign)
+            }
+        }
+
+        impl TryFrom<&MyBigInt> for $t {
+            type Error = TryFromBigIntError;
+
+            fn try_from(value: &MyBigInt) -> Result<$t, TryFromBigIntError> {
+                let magnitude = value.magnitude_u128().ok_or(TryFromBigIntError)?;
+                // Для отрицательных чисел допустим модуль на единицу больше, чем у MAX
+                let limit = if value.is_negative() { <$t>::MIN.unsigned_abs() } else { <$t>::MAX.unsigned_abs() };
+                if magnitude > limit as u128 {
+                    return Err(TryFromBigIntError);
+                }
+                let result = if value.is_negative() { (magnitude as i128).wrapping_neg() } else { magnitude as i128 };
+                Ok(result as $t)
+            }
+        }
+    )*};
+}
+
+impl_unsigned_conversions!(u8, u16, u32, u64, u128, usize);
+impl_signed_conversions!(i8, i16, i32, i64, i128, isize);
+
+// Разбор из строки: по умолчанию десятичная запись, с префиксом "0x" — шестнадцатеричная
+impl FromStr for MyBigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unsigned = s.strip_prefix(['+', '-']).unwrap_or(s);
+        if unsigned.starts_with("0x") || unsigned.starts_with("0X") {
+            MyBigInt::from_str_radix(s, 16)
+        } else {
+            MyBigInt::from_str_radix(s, 10)
+        }
+    }
+}
+
+// Арифметические и побитовые операторы.
+// Основная реализация — AddAssign<&MyBigInt> и т.п.; остальные сочетания владения операндами сводятся к ней,
+// поэтому при владеющем левом операнде его буфер разрядов переиспользуется для результата
+// (для +, -, *, &, |, ^ — и при владеющем правом).
+//
+// Внимание: у MyBigInt есть одноименные собственные методы add, sub, mul, div, rem, not, принимающие &self.
+// Если в вызывающем коде импортирован трейт (use std::ops::Add и т.п.), то для владеющего a запись a.add(&b)
+// разрешится в Add::add (метод трейта с self по значению находится раньше собственного метода с &self)
+// и переместит a. Чтобы не зависеть от импортов, используйте операторы (&a + &b) или полный путь MyBigInt::add(&a, &b).
+
+impl ops::AddAssign<&MyBigInt> for MyBigInt {
+    fn add_assign(&mut self, rhs: &MyBigInt) {
+        self.add_assign_signed(rhs, rhs.sign.clone());
+    }
+}
+
+impl ops::SubAssign<&MyBigInt> for MyBigInt {
+    fn sub_assign(&mut self, rhs: &MyBigInt) {
+        self.add_assign_signed(rhs, rhs.sign.clone().swap());
+    }
+}
+
+impl ops::MulAssign<&MyBigInt> for MyBigInt {
+    fn mul_assign(&mut self, rhs: &MyBigInt) {
+        // Множитель из одного разряда (в том числе a *= u32) умножается прямо в буфере self
+        if let [m] = rhs.digits[..] {
+            mul_digits_small_assign(&mut self.digits, m);
+        } else {
+            self.digits = mul_digits(&self.digits, &rhs.digits);
+        }
+        if rhs.is_negative() {
+            self.sign = self.sign.clone().swap();
+        }
+        self.trim();
+    }
+}
+
+impl ops::DivAssign<&MyBigInt> for MyBigInt {
+    fn div_assign(&mut self, rhs: &MyBigInt) {
+        assert!(!rhs.is_zero(), "attempt to divide by zero");
+        // Делитель из одного разряда: частное получается на месте, без нового вектора
+        if let [d] = rhs.digits[..] {
+            div_rem_digits_small(&mut self.digits, d);
+        } else {
+            self.digits = div_rem_digits(&self.digits, &rhs.digits).0;
+        }
+        if rhs.is_negative() {
+            self.sign = self.sign.clone().swap();
+        }
+        self.trim();
+    }
+}
+
+impl ops::RemAssign<&MyBigInt> for MyBigInt {
+    fn rem_assign(&mut self, rhs: &MyBigInt) {
+        assert!(!rhs.is_zero(), "attempt to divide by zero");
+        // Знак остатка совпадает со знаком делимого и не меняется
+        if let [d] = rhs.digits[..] {
+            let r = div_rem_digits_small(&mut self.digits, d);
+            self.digits.clear();
+            self.digits.push(r);
+        } else {
+            self.digits = div_rem_digits(&self.digits, &rhs.digits).1;
+        }
+        self.trim();
+    }
+}
+
+impl ops::BitAndAssign<&MyBigInt> for MyBigInt {
+    fn bitand_assign(&mut self, rhs: &MyBigInt) {
+        self.bitwise_assign(rhs, |a, b| a & b);
+    }
+}
+
+impl ops::BitOrAssign<&MyBigInt> for MyBigInt {
+    fn bitor_assign(&mut self, rhs: &MyBigInt) {
+        self.bitwise_assign(rhs, |a, b| a | b);
+    }
+}
+
+impl ops::BitXorAssign<&MyBigInt> for MyBigInt {
+    fn bitxor_assign(&mut self, rhs: &MyBigInt) {
+        self.bitwise_assign(rhs, |a, b| a ^ b);
+    }
+}
+
+// Все сочетания (&a op &b, a op &b, &a op b, a op b, a op u32) для оператора, у которого уже есть $Assign<&MyBigInt>.
+// Для &a op b можно передать выражение |a, b| ..., вычисляющее результат в буфере владеющего правого операнда b;
+// без него b только заимствуется, а результат строится из копии a.
+macro_rules! impl_binary_op {
+    ($Op:ident, $op:ident, $Assign:ident, $assign:ident) => {
+        impl_binary_op!(@common $Op, $op, $Assign, $assign);
+
+        impl ops::$Op<MyBigInt> for &MyBigInt {
+            type Output = MyBigInt;
+            fn $op(self, rhs: MyBigInt) -> MyBigInt {
+                ops::$Op::$op(self, &rhs)
+            }
+        }
+    };
+    ($Op:ident, $op:ident, $Assign:ident, $assign:ident, |$a:ident, $b:ident| $reversed:expr) => {
+        impl_binary_op!(@common $Op, $op, $Assign, $assign);
+
+        impl ops::$Op<MyBigInt> for &MyBigInt {
+            type Output = MyBigInt;
+            fn $op(self, rhs: MyBigInt) -> MyBigInt {
+                let $a = self;
+                let mut $b = rhs;
+                $reversed
+            }
+        }
+    };
+    (@common $Op:ident, $op:ident, $Assign:ident, $assign:ident) => {
+        impl ops::$Op<&MyBigInt> for &MyBigInt {
+            type Output = MyBigInt;
+            fn $op(self, rhs: &MyBigInt) -> MyBigInt {
+                let mut result = self.clone();
+                ops::$Assign::$assign(&mut result, rhs);
+                result
+            }
+        }
+
+        impl ops::$Op<&MyBigInt> for MyBigInt {
+            type Output = MyBigInt;
+            fn $op(mut self, rhs: &MyBigInt) -> MyBigInt {
+                ops::$Assign::$assign(&mut self, rhs);
+                self
+            }
+        }
+
+        impl ops::$Op<MyBigInt> for MyBigInt {
+            type Output = MyBigInt;
+            fn $op(mut self, rhs: MyBigInt) -> MyBigInt {
+                ops::$Assign::$assign(&mut self, &rhs);
+                self
+            }
+        }
+
+        impl ops::$Assign<MyBigInt> for MyBigInt {
+            fn $assign(&mut self, rhs: MyBigInt) {
+                ops::$Assign::$assign(self, &rhs);
+            }
+        }
+
+        impl ops::$Op<u32> for &MyBigInt {
+            type Output = MyBigInt;
+            fn $op(self, rhs: u32) -> MyBigInt {
+                ops::$Op::$op(self, &MyBigInt::from_limb(rhs))
+            }
+        }
+
+        impl ops::$Op<u32> for MyBigInt {
+            type Output = MyBigInt;
+            fn $op(mut self, rhs: u32) -> MyBigInt {
+                ops::$Assign::$assign(&mut self, &MyBigInt::from_limb(rhs));
+                self
+            }
+        }
+
+        impl ops::$Assign<u32> for MyBigInt {
+            fn $assign(&mut self, rhs: u32) {
+                ops::$Assign::$assign(self, &MyBigInt::from_limb(rhs));
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, AddAssign, add_assign, |a, b| { b += a; b });
+impl_binary_op!(Sub, sub, SubAssign, sub_assign, |a, b| { b -= a; -b }); // a - b = -(b - a)
+impl_binary_op!(Mul, mul, MulAssign, mul_assign, |a, b| { b *= a; b });
+impl_binary_op!(Div, div, DivAssign, div_assign);
+impl_binary_op!(Rem, rem, RemAssign, rem_assign);
+impl_binary_op!(BitAnd, bitand, BitAndAssign, bitand_assign, |a, b| { b &= a; b });
+impl_binary_op!(BitOr, bitor, BitOrAssign, bitor_assign, |a, b| { b |= a; b });
+impl_binary_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, |a, b| { b ^= a; b });
+
+impl ops::Neg for MyBigInt {
+    type Output = MyBigInt;
+    fn neg(mut self) -> MyBigInt {
+        self.sign = self.sign.swap();
+        self.trim(); // Минус ноль остается нулем
+        self
+    }
+}
+
+impl ops::Neg for &MyBigInt {
+    type Output = MyBigInt;
+    fn neg(self) -> MyBigInt {
+        -self.clone()
+    }
+}
+
+impl ops::Not for MyBigInt {
+    type Output = MyBigInt;
+    fn not(mut self) -> MyBigInt {
+        self.inv();
+        self
+    }
+}
+
+impl ops::Not for &MyBigInt {
+    type Output = MyBigInt;
+    fn not(self) -> MyBigInt {
+        MyBigInt::not(self)
+    }
+}
+
+impl ops::ShlAssign<usize> for MyBigInt {
+    fn shl_assign(&mut self, n: usize) {
+        MyBigInt::shl_assign(self, n);
+    }
+}
+
+impl ops::ShrAssign<usize> for MyBigInt {
+    fn shr_assign(&mut self, n: usize) {
+        MyBigInt::shr_assign(self, n);
+    }
+}
+
+impl ops::Shl<usize> for MyBigInt {
+    type Output = MyBigInt;
+    fn shl(mut self, n: usize) -> MyBigInt {
+        MyBigInt::shl_assign(&mut self, n);
+        self
+    }
+}
+
+impl ops::Shl<usize> for &MyBigInt {
+    type Output = MyBigInt;
+    fn shl(self, n: usize) -> MyBigInt {
+        self.shift_l(n)
+    }
+}
+
+impl ops::Shr<usize> for MyBigInt {
+    type Output = MyBigInt;
+    fn shr(mut self, n: usize) -> MyBigInt {
+        MyBigInt::shr_assign(&mut self, n);
+        self
+    }
+}
+
+impl ops::Shr<usize> for &MyBigInt {
+    type Output = MyBigInt;
+    fn shr(self, n: usize) -> MyBigInt {
+        self.shift_r(n)
     }
 }
@@ -18,15 +18,85 @@ mod tests {
         assert_eq!(big_int.get_hex(), String::from("12345"));
     }
 
+    #[test]
+    fn test_my_big_int_set_hex() {
+        let mut a = MyBigInt::new();
+        a.set_hex("-1234567890ABCDEF");
+
+        assert_eq!(a.digits, vec![0x90ABCDEF, 0x12345678]);
+        assert!(matches!(a.sign, Sign::Negative));
+        assert_eq!(a.get_hex(), String::from("-1234567890ABCDEF"));
+
+        a.set_hex("-0000");
+        assert_eq!(a.digits, vec![0]);
+        assert!(matches!(a.sign, Sign::Positive));
+        assert_eq!(a.get_hex(), String::from("0"));
+    }
+
+    #[test]
+    fn test_from_str_radix() {
+        let a = MyBigInt::from_str_radix("-0x1_0000_0000", 16).unwrap();
+        assert_eq!(a.digits, vec![0, 1]);
+        assert!(matches!(a.sign, Sign::Negative));
+
+        let b: MyBigInt = "4294967296".parse().unwrap();
+        assert_eq!(b.digits, vec![0, 1]);
+        assert!(matches!(b.sign, Sign::Positive));
+
+        let c: MyBigInt = "+0xff".parse().unwrap();
+        assert_eq!(c.digits, vec![0xFF]);
+    }
+
+    #[test]
+    fn test_from_str_radix_errors() {
+        assert_eq!(MyBigInt::from_str_radix("", 16).err(), Some(ParseBigIntError::Empty));
+        assert_eq!(MyBigInt::from_str_radix("-0x", 16).err(), Some(ParseBigIntError::Empty));
+        assert_eq!(
+            MyBigInt::from_str_radix("12G4", 16).err(),
+            Some(ParseBigIntError::InvalidDigit { position: 2, found: 'G' })
+        );
+        assert_eq!(
+            MyBigInt::from_str_radix("_1", 16).err(),
+            Some(ParseBigIntError::InvalidDigit { position: 0, found: '_' })
+        );
+        // '_' допускается только по одному между цифрами
+        assert_eq!(
+            MyBigInt::from_str_radix("1__2_", 10).err(),
+            Some(ParseBigIntError::InvalidDigit { position: 1, found: '_' })
+        );
+        assert_eq!(
+            MyBigInt::from_str_radix("12_", 10).err(),
+            Some(ParseBigIntError::InvalidDigit { position: 2, found: '_' })
+        );
+        assert_eq!(
+            MyBigInt::from_str_radix("0x_1", 16).err(),
+            Some(ParseBigIntError::InvalidDigit { position: 2, found: '_' })
+        );
+        assert_eq!(MyBigInt::from_str_radix("1_2_3", 10), Ok(MyBigInt::from(123)));
+        assert_eq!(
+            MyBigInt::from_str_radix("0x-5", 16).err(),
+            Some(ParseBigIntError::InvalidSign { position: 2 })
+        );
+
+        let mut a = MyBigInt::new();
+        assert!(a.try_set_hex("12-3").is_err());
+    }
+
     #[test]
     fn test_inv() {
         let mut a = MyBigInt::new();
         a.digits = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
         let mut b = MyBigInt::new();
-        b.digits = vec![!0, !1, !2, !3, !4, !5, !6, !7, !8, !9];
+        b.digits = vec![1, 1, 2, 3, 4, 5, 6, 7, 8, 9];
 
+        // В дополнительном коде бесконечной точности !a = -(a + 1)
         a.inv();
         assert_eq!(a.digits, b.digits);
+        assert!(matches!(a.sign, Sign::Negative));
+
+        a.inv();
+        assert_eq!(a.digits, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert!(matches!(a.sign, Sign::Positive));
     }
 
     #[test]
@@ -44,9 +114,7 @@ mod tests {
             ..Default::default()
         };
         let result = a.xor(&b);
-        let left_trimmed = &result.digits[1..];
-        let expected_trimmed = &expected_result.digits;
-        assert_eq!(left_trimmed, expected_trimmed);
+        assert_eq!(result.digits, expected_result.digits);
     }
 
 
@@ -65,9 +133,7 @@ mod tests {
             ..Default::default()
         };
         let result = a.or(&b);
-        let left_trimmed = &result.digits[1..];
-        let expected_trimmed = &expected_result.digits;
-        assert_eq!(left_trimmed, expected_trimmed);
+        assert_eq!(result.digits, expected_result.digits);
     }
 
     #[test]
@@ -82,6 +148,39 @@ mod tests {
         assert_eq!(result.digits, vec![0]);
     }
 
+    #[test]
+    fn test_bitwise_negative() {
+        let minus_one: MyBigInt = "-1".parse().unwrap();
+        let ff: MyBigInt = "0xFF".parse().unwrap();
+        assert_eq!(minus_one.and(&ff).get_hex(), String::from("FF"));
+        assert_eq!(minus_one.or(&ff).get_hex(), String::from("-1"));
+        assert_eq!(minus_one.xor(&ff).get_hex(), String::from("-100"));
+
+        let a: MyBigInt = "-6".parse().unwrap();
+        let b: MyBigInt = "3".parse().unwrap();
+        assert_eq!(a.and(&b).get_hex(), String::from("2"));
+        assert_eq!(a.or(&b).get_hex(), String::from("-5"));
+        assert_eq!(a.xor(&b).get_hex(), String::from("-7"));
+
+        let big: MyBigInt = "-0x100000000".parse().unwrap();
+        assert_eq!(big.and(&minus_one).get_hex(), String::from("-100000000"));
+        assert_eq!(big.and(&"0x1FFFFFFFF".parse().unwrap()).get_hex(), String::from("100000000"));
+
+        assert_eq!(b.not().get_hex(), String::from("-4"));
+        assert_eq!(minus_one.not().get_hex(), String::from("0"));
+    }
+
+    #[test]
+    fn test_shift_r_negative() {
+        let a: MyBigInt = "-5".parse().unwrap();
+        assert_eq!(a.shift_r(1).get_hex(), String::from("-3"));
+        let b: MyBigInt = "-4".parse().unwrap();
+        assert_eq!(b.shift_r(1).get_hex(), String::from("-2"));
+        let c: MyBigInt = "-0x100000001".parse().unwrap();
+        assert_eq!(c.shift_r(32).get_hex(), String::from("-2"));
+        assert_eq!(c.shift_r(1000).get_hex(), String::from("-1"));
+    }
+
     #[test]
     fn test_shift_r() {
         let a = MyBigInt {
@@ -89,26 +188,26 @@ mod tests {
             ..Default::default()
         };
         let expected_result = MyBigInt {
-            digits: vec![0b0000, 0b1010, 0b0101, 0b1111],
+            digits: vec![0x5000_0000, 0xF000_0000],
             ..Default::default()
         };
         let result = a.shift_r(4);
-        let left_trimmed = &result.digits[1..];
-        let expected_trimmed = &expected_result.digits;
-        assert_eq!(left_trimmed, expected_trimmed);
+        assert_eq!(result.digits, expected_result.digits);
 
         let b = MyBigInt {
             digits: vec![0b0000, 0b1010, 0b0101, 0b1111],
             ..Default::default()
         };
         let expected_result = MyBigInt {
-            digits: vec![ 0b0000, 0b1010, 0b0101, 0b1111],
+            digits: vec![0b1010, 0b0101, 0b1111],
             ..Default::default()
         };
-        let result = b.shift_r(16);
-        let left_trimmed = &result.digits[1..];
-        let expected_trimmed = &expected_result.digits;
-        assert_eq!(left_trimmed, expected_trimmed);
+        let result = b.shift_r(32);
+        assert_eq!(result.digits, expected_result.digits);
+
+        assert_eq!(b.shift_r(0).digits, b.digits);
+        assert_eq!(b.shift_r(128).digits, vec![0]);
+        assert_eq!(b.shift_r(1000).digits, vec![0]);
     }
 
     #[test]
@@ -118,26 +217,31 @@ mod tests {
             ..Default::default()
         };
         let expected_result = MyBigInt {
-            digits: vec![0b0101, 0b1111, 0b0000],
+            digits: vec![0b1010_0000, 0b0101_0000, 0b1111_0000],
             ..Default::default()
         };
         let result = a.shift_l(4);
-        let left_trimmed = &result.digits;
-        let expected_trimmed = &expected_result.digits;
-        assert_eq!(left_trimmed, expected_trimmed);
+        assert_eq!(result.digits, expected_result.digits);
 
-        let b = MyBigInt {
-            digits: vec![0b1010, 0b0101, 0b1111],
-            ..Default::default()
-        };
         let expected_result = MyBigInt {
-            digits: vec![0b1010, 0b0101, 0b1111],
+            digits: vec![0, 0, 0xA000_0000, 0x5000_0000, 0xF000_0000],
             ..Default::default()
         };
-        let result = b.shift_l(16);
-        let left_trimmed = &result.digits;
-        let expected_trimmed = &expected_result.digits;
-        assert_eq!(left_trimmed, expected_trimmed);
+        let result = a.shift_l(92);
+        assert_eq!(result.digits, expected_result.digits);
+
+        assert_eq!(a.shift_l(0).digits, a.digits);
+        assert_eq!(MyBigInt::default().shift_l(64).digits, vec![0]);
+    }
+
+    #[test]
+    fn test_shift_assign() {
+        let mut a = MyBigInt::new();
+        a.set_hex("-123456789ABCDEF");
+        a.shl_assign(100);
+        assert_eq!(a.get_hex(), String::from("-123456789ABCDEF0000000000000000000000000"));
+        a.shr_assign(100);
+        assert_eq!(a.get_hex(), String::from("-123456789ABCDEF"));
     }
 
     #[test]
@@ -206,6 +310,57 @@ mod tests {
         assert_eq!(a.sub(&b), expected);
     }
 
+    #[test]
+    fn test_sub_underflow_and_zero() {
+        let mut a = MyBigInt::new();
+        a.set_hex("64");
+        let mut b = MyBigInt::new();
+        b.set_hex("C8");
+        assert_eq!(a.sub(&b).get_hex(), String::from("-64"));
+
+        let mut c = MyBigInt::new();
+        c.set_hex("-100000000");
+        let mut d = MyBigInt::new();
+        d.set_hex("100000000");
+        let zero = c.add(&d);
+        assert_eq!(zero.digits, vec![0]);
+        assert!(matches!(zero.sign, Sign::Positive));
+        assert_eq!(c.sub(&c).get_hex(), String::from("0"));
+    }
+
+    #[test]
+    fn test_mul_signs() {
+        let mut a = MyBigInt::new();
+        a.set_hex("-FFFFFFFF");
+        let mut b = MyBigInt::new();
+        b.set_hex("FFFFFFFF");
+        assert_eq!(a.mul(&b).get_hex(), String::from("-FFFFFFFE00000001"));
+        assert_eq!(a.mul(&a).get_hex(), String::from("FFFFFFFE00000001"));
+        assert_eq!(a.mul(&MyBigInt::default()).get_hex(), String::from("0"));
+    }
+
+    #[test]
+    fn test_mul_karatsuba_matches_schoolbook() {
+        // Простой xorshift-генератор, чтобы тест был детерминированным
+        let mut state = 0x2545F4914F6CDD1Du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u32
+        };
+
+        for &(n, m) in &[(32, 32), (33, 40), (64, 64), (100, 37), (257, 300), (500, 40)] {
+            let a: Vec<u32> = (0..n).map(|_| next()).collect();
+            let b: Vec<u32> = (0..m).map(|_| next()).collect();
+            assert_eq!(mul_karatsuba(&a, &b), mul_schoolbook(&a, &b));
+
+            let ones_a = vec![u32::MAX; n];
+            let ones_b = vec![u32::MAX; m];
+            assert_eq!(mul_karatsuba(&ones_a, &ones_b), mul_schoolbook(&ones_a, &ones_b));
+        }
+    }
+
     #[test]
     fn test_modulus_positive() {
         let mut a = MyBigInt::new();
@@ -238,4 +393,827 @@ mod tests {
         expected.set_hex("2");
         assert_eq!(a.modulus(&b), expected);
     }
+
+    #[test]
+    fn test_div_rem_modes() {
+        let mut a = MyBigInt::new();
+        a.set_hex("-7");
+        let mut b = MyBigInt::new();
+        b.set_hex("3");
+
+        let (q, r) = a.div_rem(&b);
+        assert_eq!((q.get_hex(), r.get_hex()), (String::from("-2"), String::from("-1")));
+        let (q, r) = a.div_mod_floor(&b);
+        assert_eq!((q.get_hex(), r.get_hex()), (String::from("-3"), String::from("2")));
+        let (q, r) = a.div_rem_euclid(&b);
+        assert_eq!((q.get_hex(), r.get_hex()), (String::from("-3"), String::from("2")));
+
+        b.set_hex("-3");
+        a.set_hex("7");
+        assert_eq!(a.mod_floor(&b).get_hex(), String::from("-2"));
+        assert_eq!(a.rem_euclid(&b).get_hex(), String::from("1"));
+        assert_eq!(a.div_euclid(&b).get_hex(), String::from("-2"));
+    }
+
+    #[test]
+    fn test_div_rem_multi_limb() {
+        let mut a = MyBigInt::new();
+        a.set_hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF");
+        let mut b = MyBigInt::new();
+        b.set_hex("100000000000000001");
+
+        let (q, r) = a.div_rem(&b);
+        assert_eq!(q.get_hex(), String::from("FFFFFFFFFFFFFFF"));
+        assert_eq!(r.get_hex(), String::from("FF000000000000000"));
+        assert_eq!(q.mul(&b).add(&r).get_hex(), a.get_hex());
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        let mut a = MyBigInt::new();
+        a.set_hex("7");
+        assert_eq!(a.checked_div_rem(&MyBigInt::default()).err(), Some(DivisionByZero));
+    }
+
+    #[test]
+    fn test_operators() {
+        let a: MyBigInt = "0x123456789ABCDEF".parse().unwrap();
+        let b: MyBigInt = "-0xFEDCBA9876543210".parse().unwrap();
+
+        assert_eq!((&a + &b).get_hex(), MyBigInt::add(&a, &b).get_hex());
+        assert_eq!((&a - &b).get_hex(), MyBigInt::sub(&a, &b).get_hex());
+        assert_eq!((&a * &b).get_hex(), MyBigInt::mul(&a, &b).get_hex());
+        assert_eq!((&b / &a).get_hex(), MyBigInt::div(&b, &a).get_hex());
+        assert_eq!((&b % &a).get_hex(), MyBigInt::rem(&b, &a).get_hex());
+        assert_eq!((&a & &b).get_hex(), MyBigInt::and(&a, &b).get_hex());
+        assert_eq!((&a | &b).get_hex(), MyBigInt::or(&a, &b).get_hex());
+        assert_eq!((&a ^ &b).get_hex(), MyBigInt::xor(&a, &b).get_hex());
+        assert_eq!((-&a).get_hex(), String::from("-123456789ABCDEF"));
+        assert_eq!((!&b).get_hex(), String::from("FEDCBA987654320F"));
+        assert_eq!((&a << 4).get_hex(), String::from("123456789ABCDEF0"));
+        assert_eq!((&a >> 4).get_hex(), String::from("123456789ABCDE"));
+
+        // Смешанное владение операндами и правый операнд-примитив
+        let c = a.clone() * &a - b.clone() + 5u32;
+        let expected = MyBigInt::mul(&a, &a).sub(&b).add(&MyBigInt::from_str_radix("5", 10).unwrap());
+        assert_eq!(c.get_hex(), expected.get_hex());
+
+        let mut d = a.clone();
+        d += &b;
+        d -= b.clone();
+        d *= 3u32;
+        d /= 3u32;
+        d <<= 40;
+        d >>= 40;
+        assert_eq!(d.get_hex(), a.get_hex());
+        assert_eq!((-(-a.clone())).get_hex(), a.get_hex());
+        assert_eq!((-MyBigInt::default()).get_hex(), String::from("0"));
+
+        // Владеющий правый операнд при заимствованном левом
+        assert_eq!((&a - b.clone()).get_hex(), MyBigInt::sub(&a, &b).get_hex());
+        assert_eq!((&a * b.clone()).get_hex(), MyBigInt::mul(&a, &b).get_hex());
+        assert_eq!((&a & b.clone()).get_hex(), MyBigInt::and(&a, &b).get_hex());
+
+        // Составное присваивание с делителем из одного разряда и отрицательными операндами
+        let mut e = b.clone();
+        e /= &MyBigInt::from(-7);
+        assert_eq!(e.get_hex(), MyBigInt::div(&b, &MyBigInt::from(-7)).get_hex());
+        let mut e = b.clone();
+        e %= 7u32;
+        assert_eq!(e.get_hex(), MyBigInt::rem(&b, &MyBigInt::from(7)).get_hex());
+        let mut e = b.clone();
+        e ^= &a;
+        assert_eq!(e.get_hex(), MyBigInt::xor(&b, &a).get_hex());
+    }
+
+    #[test]
+    fn test_eq_ord_hash() {
+        use std::collections::HashSet;
+
+        let negative_zero = MyBigInt {
+            digits: vec![0, 0],
+            sign: Sign::Negative,
+            ..Default::default()
+        };
+        let mut zero = MyBigInt::new();
+        zero.set_hex("0");
+        assert_eq!(negative_zero, zero);
+        assert_eq!(MyBigInt::new(), zero);
+
+        let mut set = HashSet::new();
+        set.insert(negative_zero);
+        assert!(set.contains(&zero));
+
+        let a: MyBigInt = "-100000000".parse().unwrap();
+        let b: MyBigInt = "-5".parse().unwrap();
+        let c: MyBigInt = "7".parse().unwrap();
+        let mut values = vec![c.clone(), zero.clone(), a.clone(), b.clone()];
+        values.sort();
+        assert_eq!(values, vec![a.clone(), b.clone(), zero.clone(), c.clone()]);
+        assert!(a < b && b < zero && zero < c);
+        assert_eq!(a.cmp_abs(&c), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_sign_helpers() {
+        let a: MyBigInt = "-42".parse().unwrap();
+        let zero = MyBigInt::default();
+
+        assert_eq!(a.abs(), "42".parse().unwrap());
+        assert_eq!(a.signum(), "-1".parse().unwrap());
+        assert_eq!(a.abs().signum(), "1".parse().unwrap());
+        assert_eq!(zero.signum(), zero);
+        assert!(a.is_negative() && !a.is_zero());
+        assert!(zero.is_zero() && !zero.is_negative());
+    }
+
+    #[test]
+    fn test_from_primitives() {
+        assert_eq!(MyBigInt::from(0u8), MyBigInt::default());
+        assert_eq!(MyBigInt::from(u64::MAX).get_hex(), String::from("FFFFFFFFFFFFFFFF"));
+        assert_eq!(MyBigInt::from(i64::MIN).get_hex(), String::from("-8000000000000000"));
+        assert_eq!(MyBigInt::from(u128::MAX), "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF".parse().unwrap());
+        assert_eq!(MyBigInt::from(-1i8), "-1".parse().unwrap());
+        assert_eq!(MyBigInt::from(usize::MAX), MyBigInt::from(usize::MAX as u128));
+    }
+
+    #[test]
+    fn test_try_from_big_int() {
+        let a = MyBigInt::from(300u32);
+        assert_eq!(u8::try_from(&a), Err(TryFromBigIntError));
+        assert_eq!(u16::try_from(&a), Ok(300));
+        assert_eq!(i16::try_from(&-a.clone()), Ok(-300));
+        assert_eq!(u32::try_from(&-a.clone()), Err(TryFromBigIntError));
+
+        assert_eq!(i8::try_from(&MyBigInt::from(-128)), Ok(-128));
+        assert_eq!(i8::try_from(&MyBigInt::from(128)), Err(TryFromBigIntError));
+        assert_eq!(i128::try_from(&MyBigInt::from(i128::MIN)), Ok(i128::MIN));
+        assert_eq!(u128::try_from(&MyBigInt::from(u128::MAX)), Ok(u128::MAX));
+        assert_eq!(u128::try_from(&(MyBigInt::from(u128::MAX) + 1u32)), Err(TryFromBigIntError));
+    }
+
+    #[test]
+    fn test_wrapping_accessors() {
+        let a: MyBigInt = "0x1_FFFFFFFF_FFFFFFFF".parse().unwrap();
+        assert_eq!(a.to_u64_wrapping(), u64::MAX);
+        assert_eq!(a.to_i64_wrapping(), -1);
+        assert_eq!(a.to_u32_wrapping(), u32::MAX);
+
+        let b = MyBigInt::from(-2i64);
+        assert_eq!(b.to_u64_wrapping(), (-2i64) as u64);
+        assert_eq!(b.to_i32_wrapping(), -2);
+        assert_eq!(b.to_u128_wrapping(), (-2i128) as u128);
+    }
+
+    #[test]
+    fn test_to_str_radix() {
+        let a = MyBigInt::from(-255i32);
+        assert_eq!(a.to_str_radix(2), String::from("-11111111"));
+        assert_eq!(a.to_str_radix(10), String::from("-255"));
+        assert_eq!(a.to_str_radix(16), String::from("-ff"));
+        assert_eq!(a.to_str_radix(36), String::from("-73"));
+        assert_eq!(MyBigInt::default().to_str_radix(7), String::from("0"));
+        assert_eq!(MyBigInt::from(u128::MAX).to_str_radix(10), u128::MAX.to_string());
+        assert_eq!(MyBigInt::from(u128::MAX).to_str_radix(3), String::from("202201102121002021012000211012011021221022212021111001022110211020010021100121010"));
+
+        let b = MyBigInt::from_str_radix("-zz_zz", 36).unwrap();
+        assert_eq!(b, MyBigInt::from(-1679615i32));
+    }
+
+    #[test]
+    fn test_display() {
+        let a = MyBigInt::from(-1234567890123456789i64);
+        assert_eq!(format!("{}", a), String::from("-1234567890123456789"));
+        assert_eq!(format!("{:>8}", MyBigInt::from(42u8)), String::from("      42"));
+        assert_eq!(format!("{:+05}", MyBigInt::from(42u8)), String::from("+0042"));
+        assert_eq!(a.to_string().parse::<MyBigInt>().unwrap(), a);
+    }
+
+    #[test]
+    fn test_decimal_divide_and_conquer() {
+        // 10^5000 - 1 и 10^5000: длинные строки из девяток и нулей проверяют дополнение групп нулями
+        let nines = "9".repeat(5000);
+        let a: MyBigInt = nines.parse().unwrap();
+        assert_eq!(a.to_string(), nines);
+
+        let b = a + 1u32;
+        assert_eq!(b.to_string(), format!("1{}", "0".repeat(5000)));
+        assert_eq!(b.to_str_radix(16).len(), b.bits().div_ceil(4));
+    }
+
+    #[test]
+    fn test_fmt_radix_traits() {
+        let a = MyBigInt::from(0xDEADBEEFu32);
+        assert_eq!(format!("{:x}", a), String::from("deadbeef"));
+        assert_eq!(format!("{:X}", a), String::from("DEADBEEF"));
+        assert_eq!(format!("{:#x}", a), String::from("0xdeadbeef"));
+        assert_eq!(format!("{:#066x}", a), format!("{:#066x}", 0xDEADBEEFu64));
+        assert_eq!(format!("{:+#X}", a), String::from("+0xDEADBEEF"));
+        assert_eq!(format!("{:b}", MyBigInt::from(5u8)), String::from("101"));
+        assert_eq!(format!("{:#o}", MyBigInt::from(8u8)), String::from("0o10"));
+        assert_eq!(format!("{:x}", MyBigInt::default()), String::from("0"));
+
+        let b = MyBigInt::from(-255i32);
+        assert_eq!(format!("{:x}", b), String::from("-ff"));
+        assert_eq!(format!("{:#010x}", b), String::from("-0x00000ff"));
+        assert_eq!(format!("{:>8b}", MyBigInt::from(-3i8)), String::from("     -11"));
+    }
+
+    #[test]
+    fn test_debug() {
+        let a: MyBigInt = "-0x12C".parse().unwrap();
+        assert_eq!(format!("{:?}", a), String::from("MyBigInt(-0x12c)"));
+        assert_eq!(format!("{:?}", MyBigInt::new()), String::from("MyBigInt(0x0)"));
+    }
+
+    #[test]
+    fn test_bytes_unsigned() {
+        let a: MyBigInt = "0x0102030405".parse().unwrap();
+        assert_eq!(a.to_bytes_be(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(a.to_bytes_le(), vec![5, 4, 3, 2, 1]);
+        assert_eq!(MyBigInt::from_bytes_be(&[0, 0, 1, 2, 3, 4, 5]), a);
+        assert_eq!(MyBigInt::from_bytes_le(&[5, 4, 3, 2, 1, 0]), a);
+        assert_eq!(MyBigInt::default().to_bytes_be(), vec![0]);
+        assert_eq!(MyBigInt::from_bytes_be(&[]), MyBigInt::default());
+    }
+
+    #[test]
+    fn test_bytes_padded() {
+        let a: MyBigInt = "0x0102".parse().unwrap();
+        assert_eq!(a.to_bytes_be_padded(4), Ok(vec![0, 0, 1, 2]));
+        assert_eq!(a.to_bytes_be_padded(2), Ok(vec![1, 2]));
+        assert_eq!(a.to_bytes_be_padded(1), Err(IntegerTooLargeError));
+        assert_eq!(MyBigInt::default().to_bytes_be_padded(0), Ok(vec![]));
+        assert_eq!(MyBigInt::default().to_bytes_be_padded(3), Ok(vec![0, 0, 0]));
+    }
+
+    #[test]
+    fn test_bytes_signed() {
+        for &v in &[0i64, 1, -1, 127, 128, -128, -129, 255, -256, 0x7FFF_FFFF, -0x8000_0000, i64::MIN, i64::MAX] {
+            let a = MyBigInt::from(v);
+            let be = a.to_signed_bytes_be();
+            let expected: Vec<u8> = {
+                // Минимальный дополнительный код примитива для сравнения
+                let full = v.to_be_bytes();
+                let mut start = 0;
+                while start < 7
+                    && ((full[start] == 0 && full[start + 1] >> 7 == 0) || (full[start] == 0xFF && full[start + 1] >> 7 == 1))
+                {
+                    start += 1;
+                }
+                full[start..].to_vec()
+            };
+            assert_eq!(be, expected, "value {}", v);
+            assert_eq!(MyBigInt::from_signed_bytes_be(&be), a);
+            assert_eq!(MyBigInt::from_signed_bytes_le(&a.to_signed_bytes_le()), a);
+        }
+        assert_eq!(MyBigInt::from_signed_bytes_be(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFE]), MyBigInt::from(-2));
+    }
+
+    #[test]
+    fn test_modpow_small() {
+        // Учебный пример RSA: p = 61, q = 53, e = 17, d = 2753
+        let n = MyBigInt::from(3233u32);
+        let c = MyBigInt::from(65u32).modpow(&MyBigInt::from(17u32), &n);
+        assert_eq!(c, MyBigInt::from(2790u32));
+        assert_eq!(c.modpow(&MyBigInt::from(2753u32), &n), MyBigInt::from(65u32));
+
+        // Четный модуль и отрицательные основание и показатель
+        let m = MyBigInt::from(1u128 << 64);
+        assert_eq!(MyBigInt::from(7u32).modpow(&MyBigInt::from(123456789u32), &m), MyBigInt::from(17945626710895192103u64));
+        assert_eq!(MyBigInt::from(3u32).modpow(&MyBigInt::from(-5), &MyBigInt::from(1000u32)), MyBigInt::from(107u32));
+        assert_eq!(MyBigInt::from(12345u32).modpow(&MyBigInt::from(-1), &MyBigInt::from(1u64 << 61)), MyBigInt::from(676530042881490441u64));
+        assert_eq!(MyBigInt::from(-2).modpow(&MyBigInt::from(3u32), &MyBigInt::from(5u32)), MyBigInt::from(2u32));
+        assert_eq!(MyBigInt::from(5u32).modpow(&MyBigInt::default(), &MyBigInt::from(1u32)), MyBigInt::default());
+    }
+
+    #[test]
+    fn test_modpow_rsa_vector() {
+        // Модуль RSA-100 (произведение двух известных 50-значных простых), e = 65537
+        let n: MyBigInt = "0x2c8d59af47c81ab3725b472be417e3bf7ab85439af726ed3dfdf66489d155dc0b771c7a50ef7c5e58fb".parse().unwrap();
+        let d: MyBigInt = "0x29ff85cfea470484ad04c6eee532700b8d503e75af7ce9a98f65c7b98eeb6fc9abcdf8f312a89914421".parse().unwrap();
+        let e = MyBigInt::from(65537u32);
+        let message = MyBigInt::from_bytes_be(b"CryptoLib test vector");
+        let cipher: MyBigInt = "0x26f12b3009b63af06e1a9c48a98c8a8d40e90456fc4c4d4999e5794a6cee0dca01a2a8687981179ab05".parse().unwrap();
+
+        assert_eq!(message.modpow(&e, &n), cipher);
+        assert_eq!(cipher.modpow(&d, &n), message);
+    }
+
+    #[test]
+    #[should_panic(expected = "not invertible")]
+    fn test_modpow_not_invertible() {
+        MyBigInt::from(4u32).modpow(&MyBigInt::from(-1), &MyBigInt::from(8u32));
+    }
+
+    #[test]
+    fn test_montgomery_ctx() {
+        let n: MyBigInt = "0x2c8d59af47c81ab3725b472be417e3bf7ab85439af726ed3dfdf66489d155dc0b771c7a50ef7c5e58fb".parse().unwrap();
+        let ctx = MontgomeryCtx::new(&n).unwrap();
+        assert_eq!(ctx.modulus(), n);
+        assert_eq!(ctx.from_montgomery(ctx.one()), MyBigInt::from(1u32));
+
+        let a: MyBigInt = "0x123456789abcdef0123456789abcdef0123456789abcdef".parse().unwrap();
+        let b = MyBigInt::from(-987654321i64);
+        let am = ctx.to_montgomery(&a);
+        let bm = ctx.to_montgomery(&b);
+
+        let mut out = vec![0u32; ctx.limbs()];
+        let mut scratch = vec![0u32; ctx.scratch_len()];
+        ctx.mul(&mut out, &am, &bm, &mut scratch);
+        assert_eq!(ctx.from_montgomery(&out), (&a * &b).rem_euclid(&n));
+
+        ctx.sqr(&mut out, &am, &mut scratch);
+        assert_eq!(ctx.from_montgomery(&out), (&a * &a).rem_euclid(&n));
+
+        let e = MyBigInt::from(65537u32);
+        assert_eq!(ctx.from_montgomery(&ctx.pow(&am, &e)), a.modpow(&e, &n));
+    }
+
+    #[test]
+    fn test_montgomery_ctx_rejects_even_modulus() {
+        assert!(MontgomeryCtx::new(&MyBigInt::from(1u32 << 20)).is_none());
+        assert!(MontgomeryCtx::new(&MyBigInt::from(1u32)).is_none());
+        assert!(MontgomeryCtx::new(&MyBigInt::default()).is_none());
+        assert!(MontgomeryCtx::new(&MyBigInt::from(-7)).is_none());
+    }
+
+    #[test]
+    fn test_barrett_ctx() {
+        let m: MyBigInt = "0x1000000000000000000000000000000000000000000000000000000000000000".parse().unwrap();
+        let m = m - 2u32; // Четный модуль
+        let ctx = BarrettCtx::new(&m).unwrap();
+        assert_eq!(ctx.modulus(), &m);
+
+        let a: MyBigInt = "0xfedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210".parse().unwrap();
+        let a = a.rem_euclid(&m);
+        let b = MyBigInt::from(u128::MAX);
+        assert_eq!(ctx.mul(&a, &b), (&a * &b).rem_euclid(&m));
+        assert_eq!(ctx.sqr(&a), (&a * &a).rem_euclid(&m));
+        assert_eq!(ctx.reduce(&(&m * &m - 1u32)), &m - 1u32);
+        assert_eq!(ctx.reduce(&MyBigInt::from(-5)), &m - 5u32);
+
+        assert!(BarrettCtx::new(&MyBigInt::default()).is_none());
+        assert!(BarrettCtx::new(&MyBigInt::from(-4)).is_none());
+    }
+
+    #[test]
+    fn test_mod_mul() {
+        let m = MyBigInt::from(1u64 << 40);
+        let a = MyBigInt::from(-3i32);
+        let b = MyBigInt::from(0xFF_FFFF_FFFFu64);
+        assert_eq!(a.mod_mul(&b, &m), (&a * &b).rem_euclid(&m));
+        assert_eq!(MyBigInt::from(6u32).mod_mul(&MyBigInt::from(7u32), &MyBigInt::from(10u32)), MyBigInt::from(2u32));
+    }
+
+    #[test]
+    fn test_gcd() {
+        let a: MyBigInt = "0x1b2f8f9e2d4a5c6e7f8091a2b3c4d5e6f7".parse().unwrap();
+        let g = MyBigInt::from(0xFFFF_FFFBu32); // простое
+        let x = &a * &g * 12u32;
+        let y = -(&g * 18u32 * 7u32);
+        assert_eq!(x.gcd(&y), &g * 6u32 * a.gcd(&MyBigInt::from(21u32)));
+        assert_eq!(MyBigInt::from(-48).gcd(&MyBigInt::from(18)), MyBigInt::from(6));
+        assert_eq!(MyBigInt::from(0).gcd(&MyBigInt::from(-7)), MyBigInt::from(7));
+        assert_eq!(MyBigInt::from(0).gcd(&MyBigInt::from(0)), MyBigInt::from(0));
+        assert_eq!(MyBigInt::from(1u128 << 100).gcd(&MyBigInt::from(1u128 << 70)), MyBigInt::from(1u128 << 70));
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(MyBigInt::from(4).lcm(&MyBigInt::from(-6)), MyBigInt::from(12));
+        assert_eq!(MyBigInt::from(0).lcm(&MyBigInt::from(5)), MyBigInt::from(0));
+        let a = MyBigInt::from(u64::MAX);
+        let b = MyBigInt::from(u32::MAX);
+        assert_eq!(a.lcm(&b), a);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let (g, x, y) = MyBigInt::from(240).extended_gcd(&MyBigInt::from(46));
+        assert_eq!((g, x, y), (MyBigInt::from(2), MyBigInt::from(-9), MyBigInt::from(47)));
+
+        let a: MyBigInt = "-123456789012345678901234567890".parse().unwrap();
+        let b: MyBigInt = "987654321098765432109876543210".parse().unwrap();
+        let (g, x, y) = a.extended_gcd(&b);
+        assert_eq!(g, a.gcd(&b));
+        assert_eq!(&a * &x + &b * &y, g);
+
+        // Соседние числа Фибоначчи — худший случай для Евклида (все частные равны 1), проходит через шаги Лемера
+        let (mut f0, mut f1) = (MyBigInt::from(0u32), MyBigInt::from(1u32));
+        for _ in 0..500 {
+            (f0, f1) = (f1.clone(), &f0 + &f1);
+        }
+        let (g, x, y) = f1.extended_gcd(&f0);
+        assert_eq!(g, MyBigInt::from(1u32));
+        assert_eq!(&f1 * &x + &f0 * &y, g);
+        assert!(x.cmp_abs(&f0) == Ordering::Less && y.cmp_abs(&f1) == Ordering::Less);
+
+        let (g, x, y) = MyBigInt::from(-5).extended_gcd(&MyBigInt::from(0));
+        assert_eq!((g, x, y), (MyBigInt::from(5), MyBigInt::from(-1), MyBigInt::from(0)));
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        let m = MyBigInt::from(3120u32); // φ(61·53), e = 17, d = 2753
+        assert_eq!(MyBigInt::from(17u32).mod_inverse(&m), Ok(MyBigInt::from(2753u32)));
+        assert_eq!(MyBigInt::from(-17).mod_inverse(&m), Ok(MyBigInt::from(3120 - 2753)));
+        assert_eq!(MyBigInt::from(17 + 3120 * 5).mod_inverse(&m), Ok(MyBigInt::from(2753u32)));
+        assert_eq!(MyBigInt::from(-45).mod_inverse(&m), Err(NotInvertible { gcd: MyBigInt::from(15u32) }));
+        assert_eq!(MyBigInt::from(0u32).mod_inverse(&m), Err(NotInvertible { gcd: m.clone() }));
+        assert_eq!(MyBigInt::from(5u32).mod_inverse(&MyBigInt::from(1u32)), Ok(MyBigInt::from(0u32)));
+
+        let p: MyBigInt = "0xffffffff00000001000000000000000000000000ffffffffffffffffffffffff".parse().unwrap();
+        let a: MyBigInt = "0x123456789abcdef0123456789abcdef".parse().unwrap();
+        let inv = a.mod_inverse(&p).unwrap();
+        assert_eq!(a.mod_mul(&inv, &p), MyBigInt::from(1u32));
+    }
+
+    #[test]
+    fn test_batch_mod_inverse() {
+        let p: MyBigInt = "0xffffffff00000001000000000000000000000000ffffffffffffffffffffffff".parse().unwrap();
+        let values: Vec<MyBigInt> = (1..=20).map(|i| MyBigInt::from(i * 1_000_003i64 - 7_000_000)).collect();
+        let inverses = MyBigInt::batch_mod_inverse(&values, &p).unwrap();
+        for (v, inv) in values.iter().zip(&inverses) {
+            assert_eq!(inv, &v.mod_inverse(&p).unwrap());
+        }
+        assert_eq!(MyBigInt::batch_mod_inverse(&[], &p), Ok(vec![]));
+
+        let m = MyBigInt::from(100u32);
+        let values = [MyBigInt::from(3u32), MyBigInt::from(14u32), MyBigInt::from(7u32), MyBigInt::from(5u32)];
+        assert_eq!(MyBigInt::batch_mod_inverse(&values, &m), Err(NotInvertible { gcd: MyBigInt::from(2u32) }));
+    }
+
+    #[test]
+    fn test_is_probably_prime() {
+        let primes = [2u64, 3, 4093, 4099, 16_777_259, 4_294_967_291, 18_446_744_073_709_551_557];
+        let composites = [0u64, 1, 4, 4095, 16_777_261, 4_294_967_297, 18_446_744_073_709_551_615];
+        for p in primes {
+            assert!(MyBigInt::from(p).is_probably_prime(0), "{}", p);
+        }
+        for c in composites {
+            assert!(!MyBigInt::from(c).is_probably_prime(10), "{}", c);
+        }
+        assert!(!MyBigInt::from(-7).is_probably_prime(10));
+
+        // Числа Кармайкла и сильные псевдопростые: 2047 по основанию 2, 3215031751 по 2, 3, 5, 7,
+        // 3825123056546413051 по всем простым основаниям до 23
+        for c in [561u64, 41041, 825265, 2047, 3215031751, 3825123056546413051] {
+            assert!(!MyBigInt::from(c).is_probably_prime(0), "{}", c);
+        }
+
+        let m127 = (MyBigInt::from(1u32) << 127) - 1u32;
+        let m521 = (MyBigInt::from(1u32) << 521) - 1u32;
+        assert!(m127.is_probably_prime(10));
+        assert!(m521.is_probably_prime(10));
+        assert!(!((MyBigInt::from(1u32) << 67) - 1u32).is_probably_prime(10));
+        assert!(!((MyBigInt::from(1u32) << 128) + 1u32).is_probably_prime(10));
+        assert!(!(&m127 * &m521).is_probably_prime(10));
+
+        // Сильное псевдопростое по всем простым основаниям до 41; случайные основания его отсеивают
+        let spsp: MyBigInt = "3317044064679887385961981".parse().unwrap();
+        assert!(!spsp.is_probably_prime(20));
+    }
+
+    #[test]
+    fn test_is_prime_bpsw() {
+        let m127 = (MyBigInt::from(1u32) << 127) - 1u32;
+        let m521 = (MyBigInt::from(1u32) << 521) - 1u32;
+        assert!(m127.is_prime_bpsw());
+        assert!(m521.is_prime_bpsw());
+        assert!(MyBigInt::from(18_446_744_073_709_551_557u64).is_prime_bpsw());
+        assert!(!(&m127 * &m127).is_prime_bpsw());
+        assert!(!(&m127 * &m521).is_prime_bpsw());
+
+        for c in [561u64, 2047, 3215031751, 3825123056546413051] {
+            assert!(!MyBigInt::from(c).is_prime_bpsw(), "{}", c);
+        }
+        let spsp: MyBigInt = "3317044064679887385961981".parse().unwrap();
+        assert!(!spsp.is_prime_bpsw());
+        let spsp: MyBigInt = "318665857834031151167461".parse().unwrap();
+        assert!(!spsp.is_prime_bpsw());
+
+        // Сильные псевдопростые Люка проходят вторую половину теста, но не тест по основанию 2
+        for c in [5459u32, 5777, 10877, 16109, 18971] {
+            assert!(MyBigInt::from(c).is_strong_lucas_probable_prime(), "{}", c);
+            assert!(!MyBigInt::from(c).is_prime_bpsw(), "{}", c);
+        }
+    }
+
+    #[test]
+    fn test_split_mix64() {
+        let mut a = SplitMix64::new(42);
+        let mut b = SplitMix64::new(42);
+        let xs: Vec<u64> = (0..4).map(|_| a.next_u64()).collect();
+        let ys: Vec<u64> = (0..4).map(|_| b.next_u64()).collect();
+        assert_eq!(xs, ys);
+        assert_ne!(xs[0], xs[1]);
+
+        let mut bytes = [0u8; 7];
+        SplitMix64::new(1).fill_bytes(&mut bytes);
+        assert_eq!(bytes[..4], SplitMix64::new(1).next_u32().to_le_bytes());
+    }
+
+    #[test]
+    fn test_gen_prime() {
+        let mut rng = SplitMix64::new(2024);
+        for bits in [2, 3, 12, 13, 64, 256] {
+            let p = MyBigInt::gen_prime(bits, &mut rng);
+            assert_eq!(p.bits(), bits);
+            assert!(p.test_bit(bits - 2), "старшие два бита должны быть установлены");
+            assert!(p.is_prime_bpsw());
+        }
+        // Одинаковое зерно дает одинаковое простое
+        let p = MyBigInt::gen_prime(128, &mut SplitMix64::new(7));
+        assert_eq!(p, MyBigInt::gen_prime(128, &mut SplitMix64::new(7)));
+    }
+
+    #[test]
+    fn test_gen_safe_prime() {
+        let mut rng = SplitMix64::new(99);
+        for bits in [3, 10, 64, 128] {
+            let p = MyBigInt::gen_safe_prime(bits, &mut rng);
+            assert_eq!(p.bits(), bits);
+            assert!(p.is_prime_bpsw());
+            assert!((&p >> 1).is_prime_bpsw());
+        }
+    }
+
+    #[test]
+    fn test_next_prime() {
+        assert_eq!(MyBigInt::from(-10).next_prime(), MyBigInt::from(2));
+        assert_eq!(MyBigInt::from(2).next_prime(), MyBigInt::from(3));
+        assert_eq!(MyBigInt::from(4093).next_prime(), MyBigInt::from(4099));
+        assert_eq!(MyBigInt::from(4_294_967_291u64).next_prime(), MyBigInt::from(4_294_967_311u64));
+        assert_eq!(MyBigInt::from(u64::MAX).next_prime(), MyBigInt::from(18_446_744_073_709_551_629u128));
+        let m127 = (MyBigInt::from(1u32) << 127) - 1u32;
+        assert_eq!((&m127 - 1u32).next_prime(), m127);
+    }
+
+    #[test]
+    fn test_random_bits() {
+        let mut rng = SplitMix64::new(1);
+        assert_eq!(MyBigInt::random_bits(0, &mut rng), MyBigInt::from(0u32));
+        for bits in [1, 31, 32, 33, 100] {
+            let mut max_bits = 0;
+            for _ in 0..50 {
+                let x = MyBigInt::random_bits(bits, &mut rng);
+                assert!(!x.is_negative() && x.bits() <= bits);
+                max_bits = max_bits.max(x.bits());
+            }
+            assert_eq!(max_bits, bits);
+        }
+    }
+
+    #[test]
+    fn test_random_below() {
+        let mut rng = SplitMix64::new(2);
+        // Граница чуть больше степени двойки — худший случай для выборки с отклонением
+        let bound = MyBigInt::from(5u32);
+        let mut counts = [0; 5];
+        for _ in 0..5000 {
+            let x = MyBigInt::random_below(&bound, &mut rng);
+            counts[u32::try_from(&x).unwrap() as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| (900..1100).contains(&c)), "{:?}", counts);
+
+        let bound = (MyBigInt::from(1u32) << 200) + 1u32;
+        for _ in 0..20 {
+            assert!(MyBigInt::random_below(&bound, &mut rng) < bound);
+        }
+    }
+
+    #[test]
+    fn test_random_range() {
+        let mut rng = SplitMix64::new(3);
+        let (lo, hi) = (MyBigInt::from(-10), MyBigInt::from(10));
+        for _ in 0..200 {
+            let x = MyBigInt::random_range(&lo, &hi, &mut rng);
+            assert!(lo <= x && x < hi);
+        }
+        let lo = MyBigInt::from(u128::MAX);
+        assert_eq!(MyBigInt::random_range(&lo, &(&lo + 1u32), &mut rng), lo);
+
+        let a = MyBigInt::random_range(&MyBigInt::from(0u32), &hi, &mut SplitMix64::new(4));
+        assert_eq!(a, MyBigInt::random_range(&MyBigInt::from(0u32), &hi, &mut SplitMix64::new(4)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_os_rng() {
+        let mut rng = OsRng::new().unwrap();
+        let x = MyBigInt::random_bits(256, &mut rng);
+        let y = MyBigInt::random_bits(256, &mut rng);
+        assert_ne!(x, y);
+        let p = MyBigInt::gen_prime(64, &mut rng);
+        assert_eq!(p.bits(), 64);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(MyBigInt::from(0).isqrt(), MyBigInt::from(0));
+        assert_eq!(MyBigInt::from(15).isqrt(), MyBigInt::from(3));
+        assert_eq!(MyBigInt::from(16).isqrt(), MyBigInt::from(4));
+        assert_eq!(MyBigInt::from(u64::MAX).isqrt(), MyBigInt::from(u32::MAX));
+        let r: MyBigInt = "0x1f3a5b7c9d0e2f4a6b8c0d1e3f5a7b9c".parse().unwrap();
+        assert_eq!((&r * &r).isqrt(), r);
+        assert_eq!((&r * &r - 1u32).isqrt(), &r - 1u32);
+        assert_eq!((&r * &r + &r * 2u32).isqrt(), r);
+    }
+
+    #[test]
+    fn test_nth_root() {
+        assert_eq!(MyBigInt::from(1000).nth_root(3), MyBigInt::from(10));
+        assert_eq!(MyBigInt::from(999).nth_root(3), MyBigInt::from(9));
+        assert_eq!(MyBigInt::from(-1000).nth_root(3), MyBigInt::from(-10));
+        assert_eq!(MyBigInt::from(-999).nth_root(3), MyBigInt::from(-9));
+        assert_eq!(MyBigInt::from(12345).nth_root(1), MyBigInt::from(12345));
+        assert_eq!(MyBigInt::from(u128::MAX).nth_root(128), MyBigInt::from(1));
+        assert_eq!(MyBigInt::from(u128::MAX).nth_root(127), MyBigInt::from(2));
+        let x = MyBigInt::from(1u32) << 1000;
+        assert_eq!(x.nth_root(10), MyBigInt::from(1u32) << 100);
+        assert_eq!((&x - 1u32).nth_root(10), (MyBigInt::from(1u32) << 100) - 1u32);
+        assert_eq!(MyBigInt::from(7u32).nth_root(1 << 26), MyBigInt::from(1));
+        assert_eq!(MyBigInt::from(-7).nth_root((1 << 26) + 1), MyBigInt::from(-1));
+    }
+
+    #[test]
+    fn test_is_perfect_square() {
+        assert!(MyBigInt::from(0).is_perfect_square());
+        assert!(MyBigInt::new().is_perfect_square());
+        assert!(MyBigInt::from(1).is_perfect_square());
+        assert!(MyBigInt::from(144).is_perfect_square());
+        assert!(!MyBigInt::from(145).is_perfect_square());
+        assert!(!MyBigInt::from(-4).is_perfect_square());
+        let p = (MyBigInt::from(1u32) << 127) - 1u32;
+        assert!((&p * &p).is_perfect_square());
+        assert!(!(&p * (&p + 2u32)).is_perfect_square());
+    }
+
+    #[test]
+    fn test_is_perfect_power() {
+        assert_eq!(MyBigInt::from(64).is_perfect_power(), Some((MyBigInt::from(2), 6)));
+        assert_eq!(MyBigInt::from(-64).is_perfect_power(), Some((MyBigInt::from(-4), 3)));
+        assert_eq!(MyBigInt::from(-32).is_perfect_power(), Some((MyBigInt::from(-2), 5)));
+        assert_eq!(MyBigInt::from(36).is_perfect_power(), Some((MyBigInt::from(6), 2)));
+        assert_eq!(MyBigInt::from(1).is_perfect_power(), Some((MyBigInt::from(1), 2)));
+        assert_eq!(MyBigInt::from(72).is_perfect_power(), None);
+        assert_eq!(MyBigInt::from(-16).is_perfect_power(), None);
+
+        let base: MyBigInt = "1234567890123456789".parse().unwrap();
+        let x = base.pow_u32(15);
+        assert_eq!(x.is_perfect_power(), Some((base.clone(), 15)));
+        assert_eq!((&x + 1u32).is_perfect_power(), None);
+    }
+
+    #[test]
+    fn test_jacobi() {
+        // Таблица (a / 15): 15 = 3·5
+        let expected = [0, 1, 1, 0, 1, 0, 0, -1, 1, 0, 0, -1, 0, -1, -1];
+        for (a, &j) in expected.iter().enumerate() {
+            assert_eq!(MyBigInt::from(a as u32).jacobi(&MyBigInt::from(15)), j, "{}", a);
+        }
+        assert_eq!(MyBigInt::from(-1).jacobi(&MyBigInt::from(15)), -1);
+        assert_eq!(MyBigInt::from(1001).jacobi(&MyBigInt::from(9907)), -1);
+        assert_eq!(MyBigInt::from(19).jacobi(&MyBigInt::from(45)), 1);
+        assert_eq!(MyBigInt::from(7).jacobi(&MyBigInt::from(1)), 1);
+
+        let n = ((MyBigInt::from(1u32) << 61) - 1u32) * ((MyBigInt::from(1u32) << 89) - 1u32);
+        let a: MyBigInt = "0x123456789abcdef0123456789abcdef".parse().unwrap();
+        assert_eq!(a.jacobi(&n), 1);
+    }
+
+    #[test]
+    fn test_legendre() {
+        let p = (MyBigInt::from(1u32) << 127) - 1u32;
+        let a: MyBigInt = "0x123456789abcdef0123456789abcdef".parse().unwrap();
+        assert_eq!(a.legendre(&p), -1);
+        assert_eq!(MyBigInt::from(-1).legendre(&p), -1); // p ≡ 3 (mod 4)
+        assert_eq!((&a * &a).legendre(&p), 1);
+        assert_eq!((&p * 3u32).legendre(&p), 0);
+
+        // Критерий Эйлера: a^((p - 1) / 2) ≡ (a / p)
+        let euler = a.modpow(&((&p - 1u32) >> 1), &p);
+        assert_eq!(euler, &p - 1u32);
+    }
+
+    #[test]
+    fn test_kronecker() {
+        assert_eq!(MyBigInt::from(5).kronecker(&MyBigInt::from(0)), 0);
+        assert_eq!(MyBigInt::from(-1).kronecker(&MyBigInt::from(0)), 1);
+        assert_eq!(MyBigInt::from(-3).kronecker(&MyBigInt::from(-1)), -1);
+        assert_eq!(MyBigInt::from(3).kronecker(&MyBigInt::from(-1)), 1);
+        assert_eq!(MyBigInt::from(4).kronecker(&MyBigInt::from(6)), 0);
+        assert_eq!(MyBigInt::from(3).kronecker(&MyBigInt::from(2)), -1);
+        assert_eq!(MyBigInt::from(7).kronecker(&MyBigInt::from(2)), 1);
+        assert_eq!(MyBigInt::from(-5).kronecker(&MyBigInt::from(8)), -1);
+        assert_eq!(MyBigInt::from(-5).kronecker(&MyBigInt::from(-24)), 1);
+        // Для нечетного положительного n совпадает с символом Якоби
+        assert_eq!(MyBigInt::from(1001).kronecker(&MyBigInt::from(9907)), -1);
+    }
+
+    #[test]
+    fn test_sqrt_mod_prime_small() {
+        let p = MyBigInt::from(13u32);
+        assert_eq!(MyBigInt::from(10u32).sqrt_mod_prime(&p), Some((MyBigInt::from(6u32), MyBigInt::from(7u32))));
+        assert_eq!(MyBigInt::from(-3).sqrt_mod_prime(&p), Some((MyBigInt::from(6u32), MyBigInt::from(7u32))));
+        assert_eq!(MyBigInt::from(5u32).sqrt_mod_prime(&p), None);
+        assert_eq!(MyBigInt::from(26u32).sqrt_mod_prime(&p), Some((MyBigInt::from(0u32), MyBigInt::from(0u32))));
+        assert_eq!(MyBigInt::from(3u32).sqrt_mod_prime(&MyBigInt::from(2u32)), Some((MyBigInt::from(1u32), MyBigInt::from(1u32))));
+        // p ≡ 1 (mod 8): 2 = 6² mod 17
+        assert_eq!(MyBigInt::from(2u32).sqrt_mod_prime(&MyBigInt::from(17u32)), Some((MyBigInt::from(6u32), MyBigInt::from(11u32))));
+    }
+
+    #[test]
+    fn test_sqrt_mod_prime_curves() {
+        // secp256k1, p ≡ 3 (mod 4): восстановление y базовой точки по x из y² = x³ + 7
+        let p = (MyBigInt::from(1u32) << 256) - (MyBigInt::from(1u32) << 32) - 977u32;
+        let x: MyBigInt = "0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798".parse().unwrap();
+        let y: MyBigInt = "0x483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8".parse().unwrap();
+        let rhs = (x.modpow(&MyBigInt::from(3u32), &p) + 7u32).rem_euclid(&p);
+        assert_eq!(rhs.sqrt_mod_prime(&p), Some((y.clone(), &p - &y)));
+
+        // Curve25519, p ≡ 5 (mod 8)
+        let p = (MyBigInt::from(1u32) << 255) - 19u32;
+        let r: MyBigInt = "0x2b8324804fc1df0b2b4d00993dfbd7a72f431806ad2fe478c4ee1b274a0ea0b0".parse().unwrap();
+        let (r1, r2) = r.mod_mul(&r, &p).sqrt_mod_prime(&p).unwrap();
+        assert!(r1 == r || r2 == r);
+        assert_eq!(&r1 + &r2, p);
+
+        // P-224, p - 1 = q·2^96: большая степень двойки (путь Циполлы)
+        let p = (MyBigInt::from(1u32) << 224) - (MyBigInt::from(1u32) << 96) + 1u32;
+        let r: MyBigInt = "0xb70e0cbd6bb4bf7f321390b94a03c1d356c21122343280d6115c1d21".parse().unwrap();
+        let (r1, r2) = r.mod_mul(&r, &p).sqrt_mod_prime(&p).unwrap();
+        assert!(r1 == r || r2 == r);
+        let mut z = MyBigInt::from(2u32);
+        while z.legendre(&p) != -1 {
+            z += 1u32;
+        }
+        assert_eq!(z.sqrt_mod_prime(&p), None);
+    }
+
+    #[test]
+    fn test_sqrt_mod_prime_power() {
+        let seven = MyBigInt::from(7u32);
+        assert_eq!(MyBigInt::from(2u32).sqrt_mod_prime_power(&seven, 3), Some((MyBigInt::from(108u32), MyBigInt::from(235u32))));
+        assert_eq!(MyBigInt::from(2u32).sqrt_mod_prime_power(&seven, 1), Some((MyBigInt::from(3u32), MyBigInt::from(4u32))));
+        assert_eq!(MyBigInt::from(10u32).sqrt_mod_prime_power(&MyBigInt::from(13u32), 4), Some((MyBigInt::from(9834u32), MyBigInt::from(18727u32))));
+        assert_eq!(MyBigInt::from(3u32).sqrt_mod_prime_power(&seven, 5), None);
+        assert_eq!(MyBigInt::from(49u32).sqrt_mod_prime_power(&seven, 2), None);
+
+        let p = (MyBigInt::from(1u32) << 127) - 1u32;
+        let a = MyBigInt::from(123456789u32).pow_u32(2) + &p * 5u32;
+        let (r1, r2) = a.sqrt_mod_prime_power(&p, 3).unwrap();
+        let m = p.pow_u32(3);
+        assert_eq!(r1.mod_mul(&r1, &m), a.rem_euclid(&m));
+        assert_eq!(&r1 + &r2, m);
+    }
+
+    #[test]
+    fn test_crt() {
+        let nums = |v: &[i64]| v.iter().map(|&x| MyBigInt::from(x)).collect::<Vec<_>>();
+        assert_eq!(MyBigInt::crt(&nums(&[2, 3, 2]), &nums(&[3, 5, 7])), Some((MyBigInt::from(23), MyBigInt::from(105))));
+        assert_eq!(MyBigInt::crt(&nums(&[-1, 7]), &nums(&[3, 5])), Some((MyBigInt::from(2), MyBigInt::from(15))));
+        // Модули не взаимно просты: система совместна, модуль результата — lcm
+        assert_eq!(MyBigInt::crt(&nums(&[2, 4]), &nums(&[4, 6])), Some((MyBigInt::from(10), MyBigInt::from(12))));
+        assert_eq!(MyBigInt::crt(&nums(&[3, 3, 3]), &nums(&[6, 10, 15])), Some((MyBigInt::from(3), MyBigInt::from(30))));
+        assert_eq!(MyBigInt::crt(&nums(&[1, 2]), &nums(&[4, 6])), None);
+        assert_eq!(MyBigInt::crt(&[], &[]), Some((MyBigInt::from(0), MyBigInt::from(1))));
+
+        let p = (MyBigInt::from(1u32) << 127) - 1u32;
+        let q = (MyBigInt::from(1u32) << 89) - 1u32;
+        let x: MyBigInt = "0x123456789abcdef0123456789abcdef0123456789abcdef".parse().unwrap();
+        let (y, m) = MyBigInt::crt(&[x.rem_euclid(&p), x.rem_euclid(&q)], &[p.clone(), q.clone()]).unwrap();
+        assert_eq!(m, &p * &q);
+        assert_eq!(y, x.rem_euclid(&m));
+    }
+
+    #[test]
+    fn test_crt_basis_rsa() {
+        let mut rng = SplitMix64::new(2025);
+        let p = MyBigInt::gen_prime(128, &mut rng);
+        let q = MyBigInt::gen_prime(128, &mut rng);
+        let n = &p * &q;
+        let e = MyBigInt::from(65537u32);
+        let d = e.mod_inverse(&(&p - 1u32).lcm(&(&q - 1u32))).unwrap();
+
+        let basis = CrtBasis::new(&[p.clone(), q.clone()]).unwrap();
+        assert_eq!(basis.modulus(), &n);
+        // Расшифрование RSA-CRT: два возведения в степень по половинным модулям и восстановление по Гарнеру
+        for _ in 0..3 {
+            let msg = MyBigInt::random_below(&n, &mut rng);
+            let c = msg.modpow(&e, &n);
+            let m_p = c.modpow(&d.rem_euclid(&(&p - 1u32)), &p);
+            let m_q = c.modpow(&d.rem_euclid(&(&q - 1u32)), &q);
+            assert_eq!(basis.combine(&[m_p, m_q]), msg);
+        }
+
+        let moduli = [MyBigInt::from(3), MyBigInt::from(5), MyBigInt::from(7)];
+        let basis = CrtBasis::new(&moduli).unwrap();
+        assert_eq!(basis.combine(&[MyBigInt::from(2), MyBigInt::from(3), MyBigInt::from(2)]), MyBigInt::from(23));
+        assert_eq!(basis.combine(&[MyBigInt::from(-1), MyBigInt::from(-1), MyBigInt::from(-1)]), MyBigInt::from(104));
+
+        assert!(CrtBasis::new(&[MyBigInt::from(4), MyBigInt::from(6)]).is_none());
+        assert!(CrtBasis::new(&[MyBigInt::from(0)]).is_none());
+    }
 }